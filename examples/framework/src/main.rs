@@ -17,7 +17,8 @@ use std::{
 };
 use tui_additions::{
     framework::{
-        Framework, FrameworkClean, FrameworkDirection, FrameworkItem, ItemInfo, Row, RowItem, State,
+        Framework, FrameworkClean, FrameworkDirection, FrameworkItem, ItemInfo, Row, RowAlign,
+        RowItem, State,
     },
     widgets::TextList,
 };
@@ -55,29 +56,36 @@ fn run_app(terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Result<(), Box<
                 RowItem {
                     item: Box::new(TextBox::new("Test", true)),
                     width: Constraint::Length(10),
+                    z: 0,
                 },
                 RowItem {
                     item: Box::new(TextBox::new("Hello world", true)),
                     width: Constraint::Length(50),
+                    z: 0,
                 },
             ],
             centered: true,
+            align: RowAlign::Start,
             height: Constraint::Length(5),
         },
         Row {
             items: vec![RowItem {
                 item: Box::new(List::new()),
                 width: Constraint::Length(60),
+                z: 0,
             }],
             centered: true,
+            align: RowAlign::Start,
             height: Constraint::Length(10),
         },
         Row {
             items: vec![RowItem {
                 item: Box::new(KeyPressDisplay),
                 width: Constraint::Length(40),
+                z: 0,
             }],
             centered: true,
+            align: RowAlign::Start,
             height: Constraint::Length(3),
         },
     ]);
@@ -224,7 +232,7 @@ impl FrameworkItem for List {
         self.textlist
             .set_cursor_style(Style::default().fg(cursor_color));
         self.textlist.set_style(Style::default().fg(style));
-        frame.render_widget(self.textlist.clone(), inner);
+        self.textlist.render_ref(inner, frame.buffer_mut());
     }
 
     fn key_event(
@@ -76,5 +76,5 @@ fn ui(frame: &mut Frame, list: &mut TextList) {
     // set the height first
     list.set_height(frame.area().height);
     // then render it
-    frame.render_widget(list.clone(), frame.area());
+    list.render_ref(frame.area(), frame.buffer_mut());
 }
@@ -1,19 +1,28 @@
-use std::{error::Error, fmt::Display};
+use std::{
+    any::Any,
+    collections::{HashMap, VecDeque},
+    error::Error,
+    fmt::Display,
+    rc::Rc,
+};
 
-use crossterm::event::KeyEvent;
-use ratatui::{layout::Rect, Frame};
+use crossterm::event::{Event, KeyEvent, MouseEvent, MouseEventKind};
+use ratatui::{
+    layout::{Position, Rect},
+    style::Style,
+    Frame,
+};
+use typemap::{CloneMap, Key, TypeMap};
 
 use super::{
-    CursorState, FrameworkClean, FrameworkData, FrameworkDirection, FrameworkHistory, ItemInfo,
-    State,
+    CursorState, FrameworkClean, FrameworkData, FrameworkDirection, FrameworkHistory,
+    FrameworkItem, ItemInfo, MoveResult, NavAction, NavKeys, State, TabOrder, Toast, ToastAnchor,
 };
 
 /// Struct for a declarative TUI framework
 ///
 /// Copy & paste examples can be found
 /// [here](https://github.com/siriusmart/tui-additions/tree/master/examples/framework)
-
-#[derive(Clone)]
 pub struct Framework {
     /// Selectable items, auto generated when `state` is set with `new()` or `set_state()`
     pub selectables: Vec<Vec<(usize, usize)>>,
@@ -27,6 +36,75 @@ pub struct Framework {
     pub history: Vec<FrameworkHistory>,
     /// Stores the area of the previous frame
     pub frame_area: Option<Rect>,
+    /// If the framework has focus, set with `set_focused()`
+    pub focused: bool,
+    /// The order `next()` and `prev()` goes through items
+    pub tab_order: TabOrder,
+    /// How many rows the layout is scrolled down by
+    pub vertical_scroll: u16,
+    /// Moving against an edge wraps around to the other end
+    pub wrap: bool,
+    /// Cursor cannot be moved when locked, set with `lock_navigation()`
+    pub nav_locked: bool,
+    /// Moving up or down goes to the item horizontally nearest to the current item, instead of
+    /// the item with the same index in the row
+    pub align_vertical_by_pixel: bool,
+    /// Key bindings used by `handle_key()`
+    pub nav_keys: NavKeys,
+    /// Data attached to items by their location in `state`, readable by items through
+    /// `FrameworkClean`
+    pub item_meta: HashMap<(usize, usize), CloneMap>,
+    /// Set by items with `FrameworkClean::request_relayout()`, handled after passing events to
+    /// items
+    pub relayout_requested: bool,
+    /// Cursor changes since the last `drain_events()`
+    pub events: VecDeque<FrameworkEvent>,
+    /// Called whenever `state` is replaced, reset to `None` when cloned
+    pub on_screen_change: Option<ScreenChangeCallback>,
+    /// Message drawn over everything else by `render()`, the app decides when to clear it
+    pub toast: Option<Toast>,
+    /// Anchor of a range in `State` coordinates, the range goes from here to the hovered item
+    pub mark: Option<(usize, usize)>,
+    /// Used in place of `State::get_chunks()` to place items if set, it should return a chunk
+    /// for every item in the same shape as the state. `vertical_scroll` is not applied
+    pub layout_fn: Option<LayoutFn>,
+    /// Set when `state` or `selectables` changes, cleared by `render()`
+    pub layout_dirty: bool,
+}
+
+/// Same as `Framework::empty()`
+impl Default for Framework {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl Clone for Framework {
+    fn clone(&self) -> Self {
+        Self {
+            selectables: self.selectables.clone(),
+            data: self.data.clone(),
+            state: self.state.clone(),
+            cursor: self.cursor,
+            history: self.history.clone(),
+            frame_area: self.frame_area,
+            focused: self.focused,
+            tab_order: self.tab_order,
+            vertical_scroll: self.vertical_scroll,
+            wrap: self.wrap,
+            nav_locked: self.nav_locked,
+            align_vertical_by_pixel: self.align_vertical_by_pixel,
+            nav_keys: self.nav_keys.clone(),
+            item_meta: self.item_meta.clone(),
+            relayout_requested: self.relayout_requested,
+            events: self.events.clone(),
+            on_screen_change: None,
+            toast: self.toast.clone(),
+            mark: self.mark,
+            layout_fn: self.layout_fn.clone(),
+            layout_dirty: self.layout_dirty,
+        }
+    }
 }
 
 impl Framework {
@@ -42,9 +120,20 @@ impl Framework {
             data: self.data.state.clone(),
             state: self.state.clone(),
             cursor: self.cursor,
+            vertical_scroll: self.vertical_scroll,
         });
     }
 
+    /// Labels of every saved state from the oldest, followed by the current state
+    pub fn breadcrumb<F: Fn(&State) -> String>(&self, label: F) -> Vec<String> {
+        self.history
+            .iter()
+            .map(|history| &history.state)
+            .chain(std::iter::once(&self.state))
+            .map(label)
+            .collect()
+    }
+
     /// Removes the last history and returns it
     pub fn pop_history(&mut self) -> Option<FrameworkHistory> {
         self.history.pop()
@@ -61,6 +150,8 @@ impl Framework {
         self.data.state = history.data;
         self.state = history.state;
         self.cursor = history.cursor;
+        self.vertical_scroll = history.vertical_scroll;
+        self.screen_changed();
 
         Ok(())
     }
@@ -77,6 +168,8 @@ impl Framework {
         self.data.state = history.data;
         self.state = history.state;
         self.cursor = history.cursor;
+        self.vertical_scroll = history.vertical_scroll;
+        self.screen_changed();
 
         Ok(())
     }
@@ -94,6 +187,38 @@ impl Framework {
     pub fn is_none(&self) -> bool {
         self.cursor.is_none()
     }
+
+    /// If there is anything that can be selected, unlike `is_none()` which is also `true` when
+    /// there are selectable items but nothing is hovered
+    pub fn has_selectables(&self) -> bool {
+        !self.selectables.is_empty()
+    }
+
+    /// Number of rows in `self.selectables`
+    pub fn rows_selectable(&self) -> usize {
+        self.selectables.len()
+    }
+
+    /// Number of selectable items in a row of `self.selectables`, `None` if the row does not exist
+    pub fn cols_selectable(&self, row: usize) -> Option<usize> {
+        self.selectables.get(row).map(Vec::len)
+    }
+
+    /// Index of the hovered or selected item when `self.selectables` is flattened in reading order
+    pub fn current_index(&self) -> Option<usize> {
+        let (x, y) = match self.cursor {
+            CursorState::None => return None,
+            CursorState::Hover(x, y) | CursorState::Selected(x, y) => (x, y),
+        };
+
+        Some(self.selectables.iter().take(y).map(Vec::len).sum::<usize>() + x)
+    }
+
+    /// Maps an index of the flattened `self.selectables` (reading order) to the `(x, y)` in
+    /// `self.state`
+    pub fn coords_of_index(&self, index: usize) -> Option<(usize, usize)> {
+        self.selectables.iter().flatten().nth(index).copied()
+    }
 }
 
 impl Framework {
@@ -106,25 +231,239 @@ impl Framework {
             frame_area: None,
             cursor: CursorState::default(),
             history: Vec::new(),
+            focused: true,
+            tab_order: TabOrder::default(),
+            vertical_scroll: 0,
+            wrap: false,
+            nav_locked: false,
+            align_vertical_by_pixel: false,
+            nav_keys: NavKeys::default(),
+            item_meta: HashMap::new(),
+            relayout_requested: false,
+            events: VecDeque::new(),
+            on_screen_change: None,
+            toast: None,
+            mark: None,
+            layout_fn: None,
+            layout_dirty: true,
         }
     }
 
+    /// Create a Framework with an empty state
+    pub fn empty() -> Self {
+        Self::new(State::empty())
+    }
+
+    /// Return chunks of every item with `self.vertical_scroll` applied
+    ///
+    /// When scrolled, rows are laid out in their full height (see `State::content_height()`) and
+    /// then clipped to `area`, items scrolled out of `area` get a zero sized chunk
+    ///
+    /// `self.layout_fn` is used instead if it is set
+    pub fn get_chunks(&self, area: Rect) -> Vec<Vec<Rect>> {
+        if let Some(layout_fn) = &self.layout_fn {
+            return layout_fn(&self.state, area);
+        }
+
+        if self.vertical_scroll == 0 {
+            return self.state.get_chunks(area);
+        }
+
+        let content_area = Rect {
+            height: area.height.max(self.state.content_height()),
+            ..area
+        };
+
+        self.state
+            .get_chunks(content_area)
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|chunk| {
+                        let y = chunk.y as i32 - self.vertical_scroll as i32;
+                        let top = y.max(area.y as i32);
+                        let bottom = (y + chunk.height as i32).min(area.bottom() as i32);
+
+                        Rect {
+                            y: top as u16,
+                            height: (bottom - top).max(0) as u16,
+                            ..chunk
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    pub fn set_vertical_scroll(&mut self, vertical_scroll: u16) {
+        self.vertical_scroll = vertical_scroll;
+    }
+
+    /// Called when the frame is resized, so that `self.vertical_scroll` does not go past the
+    /// content and the hovered item is on screen
+    fn clamp_vertical_scroll(&mut self, area: Rect) {
+        // same layout as `get_chunks()` but without the scroll applied
+        let (content_height, chunks) = match &self.layout_fn {
+            Some(layout_fn) => {
+                let chunks = layout_fn(&self.state, area);
+                let content_height = chunks
+                    .iter()
+                    .flatten()
+                    .map(|chunk| chunk.bottom().saturating_sub(area.y))
+                    .max()
+                    .unwrap_or(0);
+                (content_height, chunks)
+            }
+            None => {
+                let content_height = self.state.content_height();
+                let content_area = Rect {
+                    height: area.height.max(content_height),
+                    ..area
+                };
+                (content_height, self.state.get_chunks(content_area))
+            }
+        };
+        let mut scroll = self
+            .vertical_scroll
+            .min(content_height.saturating_sub(area.height));
+
+        if let Some(chunk) = self
+            .cursor
+            .hover(&self.selectables)
+            .and_then(|(x, y)| chunks.get(y)?.get(x))
+        {
+            let top = chunk.y.saturating_sub(area.y);
+            let bottom = chunk.bottom().saturating_sub(area.y);
+
+            if bottom > scroll + area.height {
+                scroll = bottom - area.height;
+            }
+            scroll = scroll.min(top);
+        }
+
+        self.vertical_scroll = scroll;
+    }
+
     /// Set `self.state` and also update `self.selectables`
     pub fn set_state(&mut self, state: State) {
         self.state = state;
         self.selectables = self.state.selectables();
+        self.screen_changed();
+    }
+
+    pub fn set_on_screen_change<F: FnMut(&State) + 'static>(&mut self, on_screen_change: F) {
+        self.on_screen_change = Some(Box::new(on_screen_change));
+    }
+
+    pub fn set_layout_fn<F: Fn(&State, Rect) -> Vec<Vec<Rect>> + 'static>(&mut self, layout_fn: F) {
+        self.layout_fn = Some(Rc::new(layout_fn));
+    }
+
+    /// Calls `self.on_screen_change` with the current state
+    fn screen_changed(&mut self) {
+        self.mark = None;
+        self.layout_dirty = true;
+        if let Some(on_screen_change) = self.on_screen_change.as_mut() {
+            on_screen_change(&self.state);
+        }
+    }
+
+    /// Set `self.focused`, calls `on_framework_focus()` on every item if it changed
+    pub fn set_focused(&mut self, focused: bool) {
+        if self.focused == focused {
+            return;
+        }
+
+        self.focused = focused;
+        let (mut frameworkclean, state) = self.split_clean();
+
+        for row in state.0.iter_mut() {
+            for row_item in row.items.iter_mut() {
+                row_item
+                    .item
+                    .on_framework_focus(&mut frameworkclean, focused);
+            }
+        }
+    }
+
+    /// Describe the layout as text, does not render any item
+    ///
+    /// Each row is a line with one symbol per item: `S` for selectable, `.` for not selectable,
+    /// `H` for the hovered item and `*` for the selected item
+    pub fn debug_layout(&self) -> String {
+        let selected = self.cursor.selected(&self.selectables);
+        let hover = self.cursor.hover(&self.selectables);
+
+        let cursor = match (hover, selected) {
+            (Some((x, y)), _) => format!("hover ({x}, {y})"),
+            (_, Some((x, y))) => format!("selected ({x}, {y})"),
+            _ => String::from("none"),
+        };
+
+        let rows = self.state.0.iter().enumerate().map(|(y, row)| {
+            let items = row
+                .items
+                .iter()
+                .enumerate()
+                .map(|(x, row_item)| {
+                    if Some((x, y)) == selected {
+                        "*"
+                    } else if Some((x, y)) == hover {
+                        "H"
+                    } else if row_item.item.selectable() {
+                        "S"
+                    } else {
+                        "."
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+            format!("{y}: {items}")
+        });
+
+        std::iter::once(format!("cursor: {cursor}"))
+            .chain(rows)
+            .collect::<Vec<_>>()
+            .join("\n")
     }
 
-    /// Render every item to screen
+    /// Render every item to screen, or only the selected item if it wants to be fullscreen
     pub fn render(&mut self, frame: &mut Frame) {
         let area = frame.area();
+        if self.frame_area.is_some_and(|previous| previous != area) {
+            self.clamp_vertical_scroll(area);
+        }
         self.frame_area = Some(area);
 
-        let chunks = self.state.get_chunks(area);
+        self.render_items(frame, area);
+
+        if let Some(toast) = &self.toast {
+            toast.render(frame, area);
+        }
+
+        self.layout_dirty = false;
+    }
 
+    /// If `state` or `selectables` changed since the last `render()`, such as with `set_state()`
+    /// or `swap_items()`
+    pub fn layout_dirty(&self) -> bool {
+        self.layout_dirty
+    }
+
+    fn render_items(&mut self, frame: &mut Frame, area: Rect) {
         let selected = self.cursor.selected(&self.selectables);
         let hover = self.cursor.hover(&self.selectables);
 
+        if let Some((x, y)) = selected {
+            if self.state.get(x, y).wants_fullscreen() {
+                self.render_only_raw(frame, x, y, area, false, selected, hover);
+                self.render_only_raw(frame, x, y, area, true, selected, hover);
+                return;
+            }
+        }
+
+        let chunks = self.get_chunks(area);
+
         // actually rendering the stuff
         self.render_raw(frame, &chunks, selected, hover, false);
         self.render_raw(frame, &chunks, selected, hover, true);
@@ -139,22 +478,90 @@ impl Framework {
         hover: Option<(usize, usize)>,
         popup_render: bool,
     ) {
+        let range = self.marked_range();
         let (mut frameworkclean, state) = self.split_clean();
 
-        for (y, (row, row_chunks)) in state.0.iter_mut().zip(chunks.iter()).enumerate() {
-            for (x, (row_item, item_chunk)) in
-                row.items.iter_mut().zip(row_chunks.iter()).enumerate()
-            {
-                row_item.item.render(
+        for (x, y) in Self::render_order(state) {
+            let item_chunk = match chunks.get(y).and_then(|row_chunks| row_chunks.get(x)) {
+                Some(chunk) => *chunk,
+                None => continue,
+            };
+
+            state.get_mut(x, y).render(
+                frame,
+                &mut frameworkclean,
+                item_chunk,
+                popup_render,
+                ItemInfo {
+                    selected: Some((x, y)) == selected,
+                    hover: Some((x, y)) == hover,
+                    in_range: range.contains(&(x, y)),
+                    x,
+                    y,
+                },
+            );
+        }
+    }
+
+    /// Locations of every item in the order they should be rendered
+    fn render_order(state: &State) -> Vec<(usize, usize)> {
+        // render order sorted by z, sort is stable so items with the same z stays in order
+        let mut order = state
+            .0
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.items
+                    .iter()
+                    .enumerate()
+                    .map(move |(x, row_item)| (row_item.z, x, y))
+            })
+            .collect::<Vec<_>>();
+        order.sort_by_key(|(z, _, _)| *z);
+
+        order.into_iter().map(|(_, x, y)| (x, y)).collect()
+    }
+
+    /// Deep copy of `self.state` without the history and data
+    pub fn clone_state(&self) -> State {
+        self.state.clone()
+    }
+
+    /// Render `state` into `area` as if nothing is hovered or selected
+    ///
+    /// Items get a copy of the framework, so changes made by items while rendering are discarded
+    pub fn preview_render(&self, frame: &mut Frame, area: Rect, state: &mut State) {
+        let mut selectables = state.selectables();
+        let mut data = self.data.clone();
+        let mut cursor = CursorState::None;
+        let mut frame_area = Some(area);
+        let mut focused = self.focused;
+        let mut item_meta = HashMap::new();
+        let mut relayout_requested = false;
+        let mut frameworkclean = FrameworkClean {
+            selectables: &mut selectables,
+            data: &mut data,
+            cursor: &mut cursor,
+            frame_area: &mut frame_area,
+            focused: &mut focused,
+            item_meta: &mut item_meta,
+            relayout_requested: &mut relayout_requested,
+        };
+
+        let chunks = state.get_chunks(area);
+        let order = Self::render_order(state);
+
+        for popup_render in [false, true] {
+            for (x, y) in order.iter().copied() {
+                state.get_mut(x, y).render(
                     frame,
                     &mut frameworkclean,
-                    *item_chunk,
-                    // Some((x, y)) == selected,
-                    // Some((x, y)) == hover,
+                    chunks[y][x],
                     popup_render,
                     ItemInfo {
-                        selected: Some((x, y)) == selected,
-                        hover: Some((x, y)) == hover,
+                        selected: false,
+                        hover: false,
+                        in_range: false,
                         x,
                         y,
                     },
@@ -165,7 +572,7 @@ impl Framework {
 
     /// Render only one item
     pub fn render_only(&mut self, frame: &mut Frame, x: usize, y: usize) {
-        let chunk = self.state.get_chunks(frame.area())[y][x];
+        let chunk = self.get_chunks(frame.area())[y][x];
 
         let selected = self.cursor.selected(&self.selectables);
         let hover = self.cursor.hover(&self.selectables);
@@ -178,7 +585,7 @@ impl Framework {
     ///
     /// Location is in a format of `Vec<(x, y)>`
     pub fn render_only_multiple(&mut self, frame: &mut Frame, locations: &[(usize, usize)]) {
-        let chunks = self.state.get_chunks(frame.area());
+        let chunks = self.get_chunks(frame.area());
 
         let selected = self.cursor.selected(&self.selectables);
         let hover = self.cursor.hover(&self.selectables);
@@ -203,6 +610,7 @@ impl Framework {
         selected: Option<(usize, usize)>,
         hover: Option<(usize, usize)>,
     ) {
+        let range = self.marked_range();
         let (mut frameworkclean, state) = self.split_clean();
         state.get_mut(x, y).render(
             frame,
@@ -212,6 +620,7 @@ impl Framework {
             ItemInfo {
                 selected: selected == Some((x, y)),
                 hover: hover == Some((x, y)),
+                in_range: range.contains(&(x, y)),
                 x,
                 y,
             },
@@ -221,6 +630,7 @@ impl Framework {
     /// Send key input to selected object, returns an `Err(())` when no objct is selected
     pub fn key_input(&mut self, key: KeyEvent) -> Result<(), Box<dyn Error>> {
         let selected = self.cursor.selected(&self.selectables);
+        let range = self.marked_range();
         let (mut frameworkclean, state) = self.split_clean();
 
         if let Some((x, y)) = selected {
@@ -230,19 +640,73 @@ impl Framework {
                 ItemInfo {
                     selected: true,
                     hover: false,
+                    in_range: range.contains(&(x, y)),
                     x,
                     y,
                 },
             )?;
         }
 
+        self.relayout_if_requested();
         Ok(())
     }
 
+    /// Update `self.selectables` if an item requested it with
+    /// `FrameworkClean::request_relayout()`, the cursor is moved back into range if needed
+    pub fn relayout_if_requested(&mut self) {
+        if !self.relayout_requested {
+            return;
+        }
+
+        self.relayout_requested = false;
+        self.selectables = self.state.selectables();
+        self.layout_dirty = true;
+        self.cursor.clamp(&self.selectables);
+    }
+
+    /// Send key input to hovered object, returns `Ok(true)` if the item handled the event
+    pub fn hover_key_input(&mut self, key: KeyEvent) -> Result<bool, Box<dyn Error>> {
+        let hover = self.cursor.hover(&self.selectables);
+        let range = self.marked_range();
+        let (mut frameworkclean, state) = self.split_clean();
+
+        let used = match hover {
+            Some((x, y)) => state.get_mut(x, y).hover_key_event(
+                &mut frameworkclean,
+                key,
+                ItemInfo {
+                    selected: false,
+                    hover: true,
+                    in_range: range.contains(&(x, y)),
+                    x,
+                    y,
+                },
+            ),
+            None => Ok(false),
+        };
+
+        self.relayout_if_requested();
+        used
+    }
+
+    /// Returns `(x, y)` in `self.state` of the item at the point, selectable or not, using the
+    /// area of the previous frame. This does not change the cursor
+    pub fn item_at_point(&self, col: u16, row: u16) -> Option<(usize, usize)> {
+        let chunks = self.get_chunks(self.frame_area?);
+        let point = Position::new(col, row);
+
+        chunks.iter().enumerate().find_map(|(y, row_chunks)| {
+            row_chunks
+                .iter()
+                .position(|chunk| chunk.contains(point))
+                .map(|x| (x, y))
+        })
+    }
+
     /// Handles when mouse is clicked
     pub fn mouse_event(&mut self, col: u16, row: u16) -> bool {
         let chunks = match self.frame_area {
-            Some(area) => self.state.get_chunks(area),
+            Some(area) => self.get_chunks(area),
             None => return false,
         };
 
@@ -258,13 +722,19 @@ impl Framework {
                 // pass click event to item only if it is already selected
                 if self.cursor.selected(&self.selectables) == Some((col_no, row_no)) {
                     let (mut clean, state) = self.split_clean();
-                    return state.get_mut(x, y).mouse_event(
+                    let used = state.get_mut(x, y).mouse_event(
                         &mut clean,
                         col - chunk.x,
                         row - chunk.y,
                         col,
                         row,
                     );
+                    self.relayout_if_requested();
+                    return used;
+                }
+
+                if self.nav_locked {
+                    return false;
                 }
 
                 if self.cursor.hover(&self.selectables) == Some((col_no, row_no)) {
@@ -272,21 +742,158 @@ impl Framework {
                 }
 
                 self.deselect().ok();
+                let before = self.cursor;
                 self.cursor = CursorState::to_hover((col_no, row_no));
+                self.push_hover_event(before);
                 return true;
             }
         }
 
+        if self.nav_locked {
+            return false;
+        }
+
         self.deselect().ok();
+        let before = self.cursor;
         self.cursor = CursorState::default();
+        self.push_hover_event(before);
         true
     }
 
+    /// Push `FrameworkEvent::HoverChanged` if the hovered item is different from `before`
+    fn push_hover_event(&mut self, before: CursorState) {
+        let hover = self.cursor.hover(&self.selectables);
+        if before.hover(&self.selectables) != hover {
+            self.events.push_back(FrameworkEvent::HoverChanged(hover));
+        }
+    }
+
+    /// Show `text` over everything else until `clear_toast()` is called
+    pub fn set_toast(&mut self, text: String, style: Style, anchor: ToastAnchor) {
+        self.toast = Some(Toast {
+            text,
+            style,
+            anchor,
+        });
+    }
+
+    pub fn clear_toast(&mut self) {
+        self.toast = None;
+    }
+
+    /// Start a range at the hovered item, does nothing if nothing is hovered
+    pub fn set_mark(&mut self) {
+        if let Some(hover) = self.cursor.hover(&self.selectables) {
+            self.mark = Some(hover);
+        }
+    }
+
+    pub fn clear_mark(&mut self) {
+        self.mark = None;
+    }
+
+    /// Selectable items in the rectangle between `self.mark` and the hovered item, empty if
+    /// either is missing
+    pub fn marked_range(&self) -> Vec<(usize, usize)> {
+        let (mark, hover) = match (self.mark, self.cursor.hover(&self.selectables)) {
+            (Some(mark), Some(hover)) => (mark, hover),
+            _ => return Vec::new(),
+        };
+
+        let xs = mark.0.min(hover.0)..=mark.0.max(hover.0);
+        let ys = mark.1.min(hover.1)..=mark.1.max(hover.1);
+
+        self.selectables
+            .iter()
+            .flatten()
+            .filter(|(x, y)| xs.contains(x) && ys.contains(y))
+            .copied()
+            .collect()
+    }
+
+    /// The selected item, `None` if nothing is selected
+    pub fn selected_item_mut(&mut self) -> Option<&mut Box<dyn FrameworkItem>> {
+        let (x, y) = self.cursor.selected(&self.selectables)?;
+        Some(self.state.get_mut(x, y))
+    }
+
+    /// The hovered item, `None` if nothing is hovered
+    pub fn hovered_item_mut(&mut self) -> Option<&mut Box<dyn FrameworkItem>> {
+        let (x, y) = self.cursor.hover(&self.selectables)?;
+        Some(self.state.get_mut(x, y))
+    }
+
+    /// Locations in `State` of every selectable item in reading order
+    pub fn selectable_coords(&self) -> Vec<(usize, usize)> {
+        self.selectables.iter().flatten().copied().collect()
+    }
+
+    /// Call `f(item, x, y)` on every selectable item, `x` and `y` are locations in `State`
+    pub fn for_each_selectable<F: FnMut(&mut Box<dyn FrameworkItem>, usize, usize)>(
+        &mut self,
+        mut f: F,
+    ) {
+        for (x, y) in self.selectables.iter().flatten() {
+            f(self.state.get_mut(*x, *y), *x, *y);
+        }
+    }
+
+    /// Swap two items in `self.state` with `State::swap_items()`, then update `self.selectables`
+    /// and move the cursor back into range if needed
+    pub fn swap_items(
+        &mut self,
+        a: (usize, usize),
+        b: (usize, usize),
+    ) -> Result<(), FrameworkError> {
+        self.state.swap_items(a, b)?;
+        self.selectables = self.state.selectables();
+        self.layout_dirty = true;
+        self.cursor.clamp(&self.selectables);
+        Ok(())
+    }
+
+    /// Take all events since the last call
+    pub fn drain_events(&mut self) -> Vec<FrameworkEvent> {
+        self.events.drain(..).collect()
+    }
+
     pub fn load(&mut self) -> Result<(), Box<dyn Error>> {
         let selected = self.cursor.selected(&self.selectables);
         let hover = self.cursor.hover(&self.selectables);
+        let range = self.marked_range();
+        let (mut frameworkclean, state) = self.split_clean();
+
+        for (y, row) in state.0.iter_mut().enumerate() {
+            for (x, row_item) in row.items.iter_mut().enumerate() {
+                row_item.item.load_item(
+                    &mut frameworkclean,
+                    ItemInfo {
+                        selected: Some((x, y)) == selected,
+                        hover: Some((x, y)) == hover,
+                        in_range: range.contains(&(x, y)),
+                        x,
+                        y,
+                    },
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Same as `load()`, but calls `f(done, total)` after each item is loaded
+    pub fn load_with_progress(
+        &mut self,
+        mut f: impl FnMut(usize, usize),
+    ) -> Result<(), Box<dyn Error>> {
+        let selected = self.cursor.selected(&self.selectables);
+        let hover = self.cursor.hover(&self.selectables);
+        let range = self.marked_range();
         let (mut frameworkclean, state) = self.split_clean();
 
+        let total = state.0.iter().map(|row| row.items.len()).sum();
+        let mut done = 0;
+
         for (y, row) in state.0.iter_mut().enumerate() {
             for (x, row_item) in row.items.iter_mut().enumerate() {
                 row_item.item.load_item(
@@ -294,10 +901,13 @@ impl Framework {
                     ItemInfo {
                         selected: Some((x, y)) == selected,
                         hover: Some((x, y)) == hover,
+                        in_range: range.contains(&(x, y)),
                         x,
                         y,
                     },
                 )?;
+                done += 1;
+                f(done, total);
             }
         }
 
@@ -307,6 +917,7 @@ impl Framework {
     pub fn load_only(&mut self, x: usize, y: usize) -> Result<(), Box<dyn Error>> {
         let selected = self.cursor.selected(&self.selectables);
         let hover = self.cursor.hover(&self.selectables);
+        let range = self.marked_range();
         let (mut frameworkclean, state) = self.split_clean();
 
         state.get_mut(x, y).load_item(
@@ -314,6 +925,7 @@ impl Framework {
             ItemInfo {
                 selected: Some((x, y)) == selected,
                 hover: Some((x, y)) == hover,
+                in_range: range.contains(&(x, y)),
                 x,
                 y,
             },
@@ -323,6 +935,7 @@ impl Framework {
     pub fn load_only_multiple(&mut self, locations: &[(usize, usize)]) {
         let selected = self.cursor.selected(&self.selectables);
         let hover = self.cursor.hover(&self.selectables);
+        let range = self.marked_range();
         let (mut frameworkclean, state) = self.split_clean();
 
         locations.iter().for_each(|(x, y)| {
@@ -331,6 +944,7 @@ impl Framework {
                 ItemInfo {
                     selected: Some((*x, *y)) == selected,
                     hover: Some((*x, *y)) == hover,
+                    in_range: range.contains(&(*x, *y)),
                     x: *x,
                     y: *y,
                 },
@@ -350,16 +964,236 @@ impl Framework {
     /// Move cursor in corresponding direction, will return an `Err(E)` if something is selected
     /// and the cursor is not free to move around
     pub fn r#move(&mut self, direction: FrameworkDirection) -> Result<(), FrameworkError> {
-        self.cursor.r#move(direction, &self.selectables)
+        self.try_move(direction).map(|_| ())
+    }
+
+    /// Same as `r#move()`, but reports if the cursor moved, got blocked or wrapped around
+    pub fn try_move(
+        &mut self,
+        direction: FrameworkDirection,
+    ) -> Result<MoveResult, FrameworkError> {
+        if self.nav_locked {
+            return Err(FrameworkError::NavigationLocked);
+        }
+
+        let before = self.cursor;
+        let res = self.move_cursor(direction)?;
+        self.push_hover_event(before);
+        Ok(res)
+    }
+
+    /// Move up to `times` steps in `direction`, stopping early if blocked. Returns how many steps
+    /// the cursor moved, wrapping around counts as a step
+    pub fn repeat_move(
+        &mut self,
+        direction: FrameworkDirection,
+        times: usize,
+    ) -> Result<usize, FrameworkError> {
+        for step in 0..times {
+            if self.try_move(direction)? == MoveResult::Blocked {
+                return Ok(step);
+            }
+        }
+
+        Ok(times)
+    }
+
+    fn move_cursor(&mut self, direction: FrameworkDirection) -> Result<MoveResult, FrameworkError> {
+        let before = self.cursor.hover(&self.selectables);
+        let res = self
+            .cursor
+            .try_move(direction, &self.selectables, self.wrap)?;
+
+        let vertical = matches!(direction, FrameworkDirection::Up | FrameworkDirection::Down);
+        if !self.align_vertical_by_pixel || !vertical {
+            return Ok(res);
+        }
+
+        let (before, area, y) = match (before, self.frame_area, self.cursor) {
+            (Some(before), Some(area), CursorState::Hover(_, y)) => (before, area, y),
+            _ => return Ok(res),
+        };
+
+        // compares doubled centers to avoid rounding
+        let chunks = self.get_chunks(area);
+        let center = |(x, y): (usize, usize)| {
+            let chunk = chunks[y][x];
+            chunk.x as i32 * 2 + chunk.width as i32
+        };
+        let target = center(before);
+
+        if let Some(x) = (0..self.selectables[y].len())
+            .min_by_key(|x| (center(self.selectables[y][*x]) - target).abs())
+        {
+            self.cursor = CursorState::Hover(x, y);
+        }
+
+        Ok(res)
+    }
+
+    pub fn set_align_vertical_by_pixel(&mut self, align_vertical_by_pixel: bool) {
+        self.align_vertical_by_pixel = align_vertical_by_pixel;
+    }
+
+    pub fn set_wrap(&mut self, wrap: bool) {
+        self.wrap = wrap;
+    }
+
+    /// Hover the next item in `self.tab_order`
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Result<(), FrameworkError> {
+        if self.nav_locked {
+            return Err(FrameworkError::NavigationLocked);
+        }
+
+        let before = self.cursor;
+        self.cursor.next(self.tab_order, &self.selectables)?;
+        self.push_hover_event(before);
+        Ok(())
+    }
+
+    /// Hover the previous item in `self.tab_order`
+    pub fn prev(&mut self) -> Result<(), FrameworkError> {
+        if self.nav_locked {
+            return Err(FrameworkError::NavigationLocked);
+        }
+
+        let before = self.cursor;
+        self.cursor.prev(self.tab_order, &self.selectables)?;
+        self.push_hover_event(before);
+        Ok(())
+    }
+
+    /// Handle a key with `self.nav_keys`, returns `true` if the key is used
+    ///
+    /// When an item is selected, keys other than `NavAction::Deselect` are passed to the item
+    /// with `key_input()`. Otherwise keys without a binding are passed to the hovered item with
+    /// `hover_key_input()`
+    pub fn handle_key(&mut self, key: KeyEvent) -> Result<bool, Box<dyn Error>> {
+        Ok(self.dispatch(Event::Key(key))? != EventOutcome::Ignored)
+    }
+
+    /// Handle a key or mouse event, returns what used the event
+    ///
+    /// Keys are handled the same way as `handle_key()`, mouse clicks are passed to
+    /// `mouse_event()`, other events are ignored
+    pub fn dispatch(&mut self, event: Event) -> Result<EventOutcome, Box<dyn Error>> {
+        match event {
+            Event::Key(key) => self.dispatch_key(key),
+            Event::Mouse(MouseEvent {
+                kind: MouseEventKind::Down(_),
+                column,
+                row,
+                ..
+            }) => {
+                let selected = self.cursor.selected(&self.selectables);
+                let clicked = self.item_at_point(column, row);
+
+                Ok(match self.mouse_event(column, row) {
+                    false => EventOutcome::Ignored,
+                    true if selected.is_some() && selected == clicked => {
+                        EventOutcome::Item(clicked.unwrap())
+                    }
+                    true => EventOutcome::Navigation(self.cursor_location()),
+                })
+            }
+            _ => Ok(EventOutcome::Ignored),
+        }
+    }
+
+    fn dispatch_key(&mut self, key: KeyEvent) -> Result<EventOutcome, Box<dyn Error>> {
+        let action = self.nav_keys.get(key);
+
+        if let Some(selected) = self.cursor.selected(&self.selectables) {
+            if action == Some(NavAction::Deselect) {
+                self.deselect()?;
+                return Ok(EventOutcome::Navigation(self.cursor_location()));
+            }
+
+            self.key_input(key)?;
+            return Ok(EventOutcome::Item(selected));
+        }
+
+        match action {
+            Some(NavAction::Move(direction)) => self.r#move(direction)?,
+            Some(NavAction::Next) => self.next()?,
+            Some(NavAction::Prev) => self.prev()?,
+            Some(NavAction::Select) if self.is_hover() => self.select()?,
+            Some(NavAction::Select) | Some(NavAction::Deselect) => {
+                return Ok(EventOutcome::Ignored)
+            }
+            None => {
+                let hover = self.cursor.hover(&self.selectables);
+                return Ok(match (self.hover_key_input(key)?, hover) {
+                    (true, Some(hover)) => EventOutcome::Item(hover),
+                    _ => EventOutcome::Ignored,
+                });
+            }
+        }
+
+        Ok(EventOutcome::Navigation(self.cursor_location()))
+    }
+
+    /// Location in `self.state` of the hovered or selected item
+    fn cursor_location(&self) -> Option<(usize, usize)> {
+        self.cursor
+            .hover(&self.selectables)
+            .or(self.cursor.selected(&self.selectables))
+    }
+
+    /// Attach `value` to the item at `(x, y)` in `self.state`
+    pub fn set_item_meta<K: Key>(&mut self, x: usize, y: usize, value: K::Value)
+    where
+        K::Value: Clone,
+    {
+        self.item_meta
+            .entry((x, y))
+            .or_insert_with(TypeMap::custom)
+            .insert::<K>(value);
+    }
+
+    /// Value attached to the item at `(x, y)` with `set_item_meta()`
+    pub fn get_item_meta<K: Key>(&self, x: usize, y: usize) -> Option<&K::Value>
+    where
+        K::Value: Clone,
+    {
+        self.item_meta.get(&(x, y))?.get::<K>()
+    }
+
+    pub fn set_nav_keys(&mut self, nav_keys: NavKeys) {
+        self.nav_keys = nav_keys;
+    }
+
+    /// Stop the cursor from moving, `key_input()` still goes to the selected item
+    pub fn lock_navigation(&mut self, nav_locked: bool) {
+        self.nav_locked = nav_locked;
+    }
+
+    pub fn set_tab_order(&mut self, tab_order: TabOrder) {
+        self.tab_order = tab_order;
     }
 
     /// Select the hovering item
     pub fn select(&mut self) -> Result<(), Box<dyn Error>> {
+        self.select_item(|item, framework| item.select(framework))
+    }
+
+    /// Same as `select()`, but the item gets `payload` in `FrameworkItem::select_with()`
+    pub fn select_with<T: Any>(&mut self, payload: T) -> Result<(), Box<dyn Error>> {
+        self.select_item(|item, framework| item.select_with(framework, &payload))
+    }
+
+    /// Select the hovering item if `select(item, framework)` returns `true`
+    fn select_item<F: FnOnce(&mut Box<dyn FrameworkItem>, &mut FrameworkClean) -> bool>(
+        &mut self,
+        select: F,
+    ) -> Result<(), Box<dyn Error>> {
         if let Some((x, y)) = self.cursor.hover(&self.selectables) {
             let (mut frameworkclean, state) = self.split_clean();
             let item = state.get_mut(x, y);
-            if item.select(&mut frameworkclean) {
+            if select(item, &mut frameworkclean) {
                 self.cursor.select()?;
+                self.events.push_back(FrameworkEvent::Selected((x, y)));
             }
         } else {
             Err(FrameworkError::CursorStateMismatch)?;
@@ -375,6 +1209,7 @@ impl Framework {
             let item = state.get_mut(x, y);
             if item.deselect(&mut frameworkclean) {
                 self.cursor.deselect()?;
+                self.events.push_back(FrameworkEvent::Deselected((x, y)));
             }
         } else {
             Err(FrameworkError::CursorStateMismatch)?;
@@ -384,6 +1219,35 @@ impl Framework {
     }
 }
 
+/// What used the event in `Framework::dispatch()`, coordinates are locations in `State`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EventOutcome {
+    /// Nothing used the event
+    Ignored,
+    /// The item at the location used the event
+    Item((usize, usize)),
+    /// The cursor moved, got selected or deselected, with the location of the cursor afterwards
+    Navigation(Option<(usize, usize)>),
+}
+
+/// Changes to the cursor, taken with `Framework::drain_events()`, coordinates are locations in
+/// `State`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FrameworkEvent {
+    /// A different item (or nothing) is hovered
+    HoverChanged(Option<(usize, usize)>),
+    /// The item got selected
+    Selected((usize, usize)),
+    /// The item got deselected
+    Deselected((usize, usize)),
+}
+
+/// Callback for `Framework.on_screen_change`
+pub type ScreenChangeCallback = Box<dyn FnMut(&State)>;
+
+/// Custom layout for `Framework.layout_fn`
+pub type LayoutFn = Rc<dyn Fn(&State, Rect) -> Vec<Vec<Rect>>>;
+
 /// Errors that may be returned by `Framework`
 #[derive(Debug)]
 pub enum FrameworkError {
@@ -393,6 +1257,10 @@ pub enum FrameworkError {
     CursorStateMismatch,
     /// Not found in `self.history`, caused by incorrect index or `self.history` is empty
     NoSuchSave,
+    /// Moving the cursor when navigation is locked with `lock_navigation()`
+    NavigationLocked,
+    /// There is no item at the location in `State`
+    NoSuchItem,
 }
 
 impl Display for FrameworkError {
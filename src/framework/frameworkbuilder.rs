@@ -0,0 +1,62 @@
+use super::{Framework, NavKeys, State, TabOrder};
+
+/// Builder for a `Framework` with its options set, created with `Framework::builder()`
+#[derive(Clone, Default)]
+pub struct FrameworkBuilder(Framework);
+
+impl FrameworkBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn state(mut self, state: State) -> Self {
+        self.0.set_state(state);
+        self
+    }
+
+    pub fn tab_order(mut self, tab_order: TabOrder) -> Self {
+        self.0.set_tab_order(tab_order);
+        self
+    }
+
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.0.set_wrap(wrap);
+        self
+    }
+
+    pub fn nav_locked(mut self, nav_locked: bool) -> Self {
+        self.0.lock_navigation(nav_locked);
+        self
+    }
+
+    pub fn align_vertical_by_pixel(mut self, align_vertical_by_pixel: bool) -> Self {
+        self.0.set_align_vertical_by_pixel(align_vertical_by_pixel);
+        self
+    }
+
+    pub fn nav_keys(mut self, nav_keys: NavKeys) -> Self {
+        self.0.set_nav_keys(nav_keys);
+        self
+    }
+
+    pub fn vertical_scroll(mut self, vertical_scroll: u16) -> Self {
+        self.0.set_vertical_scroll(vertical_scroll);
+        self
+    }
+
+    pub fn focused(mut self, focused: bool) -> Self {
+        self.0.focused = focused;
+        self
+    }
+
+    pub fn build(self) -> Framework {
+        self.0
+    }
+}
+
+impl Framework {
+    /// Start building a framework with an empty state
+    pub fn builder() -> FrameworkBuilder {
+        FrameworkBuilder::new()
+    }
+}
@@ -1,4 +1,7 @@
+use std::collections::HashMap;
+
 use ratatui::layout::Rect;
+use typemap::CloneMap;
 
 use super::{CursorState, Framework, FrameworkData, State};
 
@@ -8,6 +11,17 @@ pub struct FrameworkClean<'a> {
     pub data: &'a mut FrameworkData,
     pub cursor: &'a mut CursorState,
     pub frame_area: &'a mut Option<Rect>,
+    pub focused: &'a mut bool,
+    pub item_meta: &'a mut HashMap<(usize, usize), CloneMap>,
+    pub relayout_requested: &'a mut bool,
+}
+
+impl FrameworkClean<'_> {
+    /// Ask the framework to update which items are selectable after the current event, such as
+    /// when an item changed its `selectable()`
+    pub fn request_relayout(&mut self) {
+        *self.relayout_requested = true;
+    }
 }
 
 impl<'a> From<&'a mut Framework> for (FrameworkClean<'a>, &'a mut State) {
@@ -18,6 +32,9 @@ impl<'a> From<&'a mut Framework> for (FrameworkClean<'a>, &'a mut State) {
             data: &mut original.data,
             cursor: &mut original.cursor,
             frame_area: &mut original.frame_area,
+            focused: &mut original.focused,
+            item_meta: &mut original.item_meta,
+            relayout_requested: &mut original.relayout_requested,
         };
 
         (frameworkclean, state)
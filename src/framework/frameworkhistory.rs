@@ -1,6 +1,8 @@
+use std::collections::{HashMap, VecDeque};
+
 use typemap::{CloneMap, TypeMap};
 
-use super::{CursorState, Framework, FrameworkData, State};
+use super::{CursorState, Framework, FrameworkData, NavKeys, State, TabOrder};
 
 /// Save state for Framework
 #[derive(Clone)]
@@ -13,6 +15,8 @@ pub struct FrameworkHistory {
     pub state: State,
     /// The state and position of cursor
     pub cursor: CursorState,
+    /// How many rows the layout is scrolled down by
+    pub vertical_scroll: u16,
 }
 
 impl From<FrameworkHistory> for Framework {
@@ -24,6 +28,21 @@ impl From<FrameworkHistory> for Framework {
             cursor: original.cursor,
             history: Vec::new(),
             frame_area: None,
+            focused: true,
+            tab_order: TabOrder::default(),
+            vertical_scroll: original.vertical_scroll,
+            wrap: false,
+            nav_locked: false,
+            align_vertical_by_pixel: false,
+            nav_keys: NavKeys::default(),
+            item_meta: HashMap::new(),
+            relayout_requested: false,
+            events: VecDeque::new(),
+            on_screen_change: None,
+            toast: None,
+            mark: None,
+            layout_fn: None,
+            layout_dirty: true,
         }
     }
 }
@@ -19,10 +19,22 @@ pub trait FrameworkItem: DynClone + Any {
         true
     }
 
+    /// Same as `select()` but with a payload from `Framework::select_with()`, calls `select()` by
+    /// default
+    fn select_with(&mut self, framework: &mut FrameworkClean, payload: &dyn Any) -> bool {
+        self.select(framework)
+    }
+
     /// Deselect the item, if `Ok(())` is return means deselect is successful, or else it failed
     fn deselect(&mut self, framework: &mut FrameworkClean) -> bool {
         true
     }
+
+    /// If the item should take up the whole frame when selected (instead of its own chunk)
+    fn wants_fullscreen(&self) -> bool {
+        false
+    }
+
     fn render(
         &mut self,
         frame: &mut Frame,
@@ -33,6 +45,9 @@ pub trait FrameworkItem: DynClone + Any {
     ) {
     }
 
+    /// Runs when the focus of the framework changes with `Framework.set_focused()`
+    fn on_framework_focus(&mut self, framework: &mut FrameworkClean, focused: bool) {}
+
     /// Runs when `Framework.load_item()` is called
     fn load_item(
         &mut self,
@@ -52,6 +67,17 @@ pub trait FrameworkItem: DynClone + Any {
         Ok(())
     }
 
+    /// Handles key event when the item is hovered but not selected, returns `true` if the event
+    /// is handled
+    fn hover_key_event(
+        &mut self,
+        framework: &mut FrameworkClean,
+        key: KeyEvent,
+        info: ItemInfo,
+    ) -> Result<bool, Box<dyn Error>> {
+        Ok(false)
+    }
+
     fn mouse_event(
         &mut self,
         framework: &mut FrameworkClean,
@@ -10,3 +10,13 @@ mod frameworkhistory;
 pub use frameworkhistory::*;
 mod frameworkdata;
 pub use frameworkdata::*;
+mod navkeys;
+pub use navkeys::*;
+mod toast;
+pub use toast::*;
+mod frameworkbuilder;
+pub use frameworkbuilder::*;
+#[cfg(feature = "run")]
+mod run;
+#[cfg(feature = "run")]
+pub use run::*;
@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use super::FrameworkDirection;
+
+/// What a key does in `Framework::handle_key()`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum NavAction {
+    Move(FrameworkDirection),
+    Next,
+    Prev,
+    Select,
+    Deselect,
+}
+
+/// Key bindings for `Framework::handle_key()`, keys are matched by code and modifiers
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct NavKeys(pub HashMap<KeyEvent, NavAction>);
+
+impl Default for NavKeys {
+    /// Arrow keys to move, Tab and Shift+Tab for next and previous, Enter to select and Esc to
+    /// deselect
+    fn default() -> Self {
+        Self::empty()
+            .bind(
+                KeyCode::Up,
+                KeyModifiers::NONE,
+                NavAction::Move(FrameworkDirection::Up),
+            )
+            .bind(
+                KeyCode::Down,
+                KeyModifiers::NONE,
+                NavAction::Move(FrameworkDirection::Down),
+            )
+            .bind(
+                KeyCode::Left,
+                KeyModifiers::NONE,
+                NavAction::Move(FrameworkDirection::Left),
+            )
+            .bind(
+                KeyCode::Right,
+                KeyModifiers::NONE,
+                NavAction::Move(FrameworkDirection::Right),
+            )
+            .bind(KeyCode::Tab, KeyModifiers::NONE, NavAction::Next)
+            .bind(KeyCode::BackTab, KeyModifiers::SHIFT, NavAction::Prev)
+            .bind(KeyCode::Enter, KeyModifiers::NONE, NavAction::Select)
+            .bind(KeyCode::Esc, KeyModifiers::NONE, NavAction::Deselect)
+    }
+}
+
+impl NavKeys {
+    /// No key bindings
+    pub fn empty() -> Self {
+        Self(HashMap::new())
+    }
+
+    pub fn bind(mut self, code: KeyCode, modifiers: KeyModifiers, action: NavAction) -> Self {
+        self.set_bind(code, modifiers, action);
+        self
+    }
+
+    pub fn set_bind(&mut self, code: KeyCode, modifiers: KeyModifiers, action: NavAction) {
+        self.0.insert(KeyEvent::new(code, modifiers), action);
+    }
+
+    /// Action bound to `key`, the kind and state of `key` are ignored
+    pub fn get(&self, key: KeyEvent) -> Option<NavAction> {
+        self.0.get(&KeyEvent::new(key.code, key.modifiers)).copied()
+    }
+}
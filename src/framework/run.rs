@@ -0,0 +1,79 @@
+use std::{
+    error::Error,
+    io::{self, stdout},
+    ops::ControlFlow,
+};
+
+use crossterm::{
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{backend::Backend, Frame, Terminal};
+
+use super::Framework;
+
+/// Enables raw mode, alternate screen and mouse capture, everything is restored when dropped
+/// (including when panicking)
+pub struct TerminalGuard;
+
+impl TerminalGuard {
+    pub fn new() -> io::Result<Self> {
+        enable_raw_mode()?;
+        execute!(stdout(), EnterAlternateScreen, EnableMouseCapture)?;
+        Ok(Self)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        _ = disable_raw_mode();
+        _ = execute!(stdout(), LeaveAlternateScreen, DisableMouseCapture);
+    }
+}
+
+/// Set up the terminal and run `framework` until `handle` returns `ControlFlow::Break`
+///
+/// * `draw` is called every frame, usually `|framework, frame| framework.render(frame)`
+/// * `handle` is called with every event read from the terminal
+pub fn run<B, D, H>(
+    framework: &mut Framework,
+    backend: B,
+    draw: D,
+    handle: H,
+) -> Result<(), Box<dyn Error>>
+where
+    B: Backend,
+    D: FnMut(&mut Framework, &mut Frame),
+    H: FnMut(&mut Framework, Event) -> Result<ControlFlow<()>, Box<dyn Error>>,
+{
+    let _guard = TerminalGuard::new()?;
+    let mut terminal = Terminal::new(backend)?;
+
+    let res = run_loop(framework, &mut terminal, event::read, draw, handle);
+    terminal.show_cursor()?;
+    res
+}
+
+/// The loop in `run()` without setting up the terminal, events are taken from `next_event`
+pub fn run_loop<B, E, D, H>(
+    framework: &mut Framework,
+    terminal: &mut Terminal<B>,
+    mut next_event: E,
+    mut draw: D,
+    mut handle: H,
+) -> Result<(), Box<dyn Error>>
+where
+    B: Backend,
+    E: FnMut() -> io::Result<Event>,
+    D: FnMut(&mut Framework, &mut Frame),
+    H: FnMut(&mut Framework, Event) -> Result<ControlFlow<()>, Box<dyn Error>>,
+{
+    loop {
+        terminal.draw(|frame| draw(framework, frame))?;
+
+        if handle(framework, next_event()?)?.is_break() {
+            return Ok(());
+        }
+    }
+}
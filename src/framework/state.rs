@@ -8,6 +8,9 @@ pub struct RowItem {
     pub item: Box<dyn FrameworkItem>,
     /// Width of the item
     pub width: Constraint,
+    /// Items with a higher `z` are rendered later (on top), items with the same `z` are rendered
+    /// in order. This does not affect selection
+    pub z: i32,
 }
 
 /// Contains a row of objects
@@ -15,17 +18,76 @@ pub struct RowItem {
 pub struct Row {
     /// All the items in the row
     pub items: Vec<RowItem>,
-    /// If the row should be centered or not
+    /// If the row should be centered or not, same as `align` being `RowAlign::Center`. Any
+    /// `align` other than `RowAlign::Start` takes precedence
     pub centered: bool,
+    /// How items are placed in the row
+    pub align: RowAlign,
     /// The height of the row
     pub height: Constraint,
 }
 
+impl Row {
+    /// The alignment of the row, taking `centered` into account
+    pub fn alignment(&self) -> RowAlign {
+        match self.align {
+            RowAlign::Start if self.centered => RowAlign::Center,
+            align => align,
+        }
+    }
+}
+
+/// How items are placed in a row
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum RowAlign {
+    /// All items are placed to the left
+    #[default]
+    Start,
+    /// All items are placed in the middle
+    Center,
+    /// All items are placed to the right
+    End,
+    /// The first and last items are at the edges, the space left is split evenly between items
+    SpaceBetween,
+}
+
 /// Contains the items and the layout of the TUI
 #[derive(Clone)]
 pub struct State(pub Vec<Row>);
 
 impl State {
+    /// A state with no rows
+    pub fn empty() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Create a state where every item has the same width and height
+    pub fn grid(
+        items: Vec<Vec<Box<dyn FrameworkItem>>>,
+        width: Constraint,
+        height: Constraint,
+        centered: bool,
+    ) -> Self {
+        Self(
+            items
+                .into_iter()
+                .map(|row| Row {
+                    items: row
+                        .into_iter()
+                        .map(|item| RowItem { item, width, z: 0 })
+                        .collect(),
+                    centered: false,
+                    align: if centered {
+                        RowAlign::Center
+                    } else {
+                        RowAlign::Start
+                    },
+                    height,
+                })
+                .collect(),
+        )
+    }
+
     /// Generate selectables which is a 2D vector of items that can be selected
     ///
     /// `(usize, usize)` maps to the `(x, y)` position to `State.0`, items that are not selectable
@@ -48,6 +110,18 @@ impl State {
         selectables
     }
 
+    /// Minimum height to fit every row without shrinking any of them, only `Length` and `Min`
+    /// heights are counted
+    pub fn content_height(&self) -> u16 {
+        self.0
+            .iter()
+            .map(|row| match row.height {
+                Constraint::Length(height) | Constraint::Min(height) => height,
+                _ => 0,
+            })
+            .fold(0, u16::saturating_add)
+    }
+
     /// Return chunks as 2D array of rects
     pub fn get_chunks(&self, area: Rect) -> Vec<Vec<Rect>> {
         // chunks
@@ -64,42 +138,54 @@ impl State {
             .iter()
             .skip(1)
             .take(row_constraints_length)
-            .zip(self.0.iter().map(|row| {
-                let begin_length = if row.centered {
-                    Constraint::Length(
-                        (area.width
-                            - row
-                                .items
-                                .iter()
-                                .map(|item| item.width.apply(area.width))
-                                .sum::<u16>())
-                            / 2,
-                    )
-                } else {
-                    Constraint::Length(0)
-                };
-
-                let mut out = vec![begin_length];
-                out.extend(row.items.iter().map(|item| item.width));
-                out.push(Constraint::Length(0));
-                out
-            }))
-            .map(|(row_chunk, constraints)| {
-                let constraints_length = constraints.len() - 2;
-
+            .zip(self.0.iter())
+            .map(|(row_chunk, row)| {
                 Layout::default()
                     .direction(Direction::Horizontal)
-                    .constraints(constraints)
+                    .constraints(Self::row_constraints(row, area.width))
                     .split(*row_chunk)
                     .iter()
                     .skip(1)
-                    .take(constraints_length)
+                    .step_by(2)
+                    .take(row.items.len())
                     .copied()
                     .collect()
             })
             .collect::<Vec<_>>()
     }
 
+    /// Horizontal constraints of a row, items are the odd items with spacings between them
+    fn row_constraints(row: &Row, width: u16) -> Vec<Constraint> {
+        let free = width.saturating_sub(
+            row.items
+                .iter()
+                .map(|item| item.width.apply(width))
+                .sum::<u16>(),
+        );
+        let gaps = row.items.len().saturating_sub(1) as u16;
+
+        let (begin, gap) = match row.alignment() {
+            RowAlign::Start => (0, 0),
+            RowAlign::Center => (free / 2, 0),
+            RowAlign::End => (free, 0),
+            RowAlign::SpaceBetween if gaps == 0 => (0, 0),
+            RowAlign::SpaceBetween => (0, free / gaps),
+        };
+        // for space between, the first few gaps get 1 more if it cannot be evenly divided
+        let remainder = if gaps == 0 { 0 } else { free % gaps };
+
+        let mut out = vec![Constraint::Length(begin)];
+        row.items.iter().enumerate().for_each(|(x, item)| {
+            if x != 0 {
+                let extra = row.alignment() == RowAlign::SpaceBetween && (x as u16) <= remainder;
+                out.push(Constraint::Length(gap + u16::from(extra)));
+            }
+            out.push(item.width);
+        });
+        out.push(Constraint::Length(0));
+        out
+    }
+
     /// Get reference to item with x and y value
     pub fn get(&self, x: usize, y: usize) -> &dyn FrameworkItem {
         &*self.0[y].items[x].item
@@ -109,12 +195,41 @@ impl State {
     pub fn get_mut(&mut self, x: usize, y: usize) -> &mut Box<dyn FrameworkItem> {
         &mut self.0[y].items[x].item
     }
+
+    /// Swap the `RowItem`s at `(x, y)` locations `a` and `b`, including their widths and `z`
+    pub fn swap_items(
+        &mut self,
+        a: (usize, usize),
+        b: (usize, usize),
+    ) -> Result<(), FrameworkError> {
+        let exists = |(x, y): (usize, usize)| self.0.get(y).is_some_and(|row| x < row.items.len());
+        if !exists(a) || !exists(b) {
+            return Err(FrameworkError::NoSuchItem);
+        }
+
+        if a.1 == b.1 {
+            self.0[a.1].items.swap(a.0, b.0);
+            return Ok(());
+        }
+
+        let (top, bottom) = if a.1 < b.1 { (a, b) } else { (b, a) };
+        let (before, after) = self.0.split_at_mut(bottom.1);
+        std::mem::swap(
+            &mut before[top.1].items[top.0],
+            &mut after[0].items[bottom.0],
+        );
+        Ok(())
+    }
 }
 
 /// State of cursor
 ///
 /// The 2 numbers represent the x and y in `Framework.selectables` rather than `State.0`
-#[derive(Clone, Copy, PartialEq, Eq)]
+///
+/// Constructing `Hover` or `Selected` directly with indices that are not in
+/// `Framework.selectables` will cause a panic when the cursor is used, prefer
+/// `CursorState::hover_checked()` and `CursorState::selected_checked()`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum CursorState {
     /// Nothing is selected
     None,
@@ -176,6 +291,42 @@ impl CursorState {
         Self::Selected(location.0, location.1)
     }
 
+    /// Create a hovering cursor, returns `None` if the location is not in `selectables`
+    pub fn hover_checked(x: usize, y: usize, selectables: &[Vec<(usize, usize)>]) -> Option<Self> {
+        Self::in_selectables(selectables, (x, y)).then_some(Self::Hover(x, y))
+    }
+
+    /// Create a selected cursor, returns `None` if the location is not in `selectables`
+    pub fn selected_checked(
+        x: usize,
+        y: usize,
+        selectables: &[Vec<(usize, usize)>],
+    ) -> Option<Self> {
+        Self::in_selectables(selectables, (x, y)).then_some(Self::Selected(x, y))
+    }
+
+    /// Move an out of range cursor back into `selectables`, the cursor will be reset to `None` if
+    /// `selectables` is empty
+    pub fn clamp(&mut self, selectables: &[Vec<(usize, usize)>]) {
+        let (x, y) = match self {
+            Self::None => return,
+            Self::Hover(x, y) | Self::Selected(x, y) => (x, y),
+        };
+
+        if selectables.is_empty() {
+            *self = Self::None;
+            return;
+        }
+
+        *y = (*y).min(selectables.len() - 1);
+        *x = (*x).min(selectables[*y].len() - 1);
+    }
+
+    fn in_selectables(selectables: &[Vec<(usize, usize)>], location: (usize, usize)) -> bool {
+        let (x, y) = location;
+        selectables.get(y).is_some_and(|row| x < row.len())
+    }
+
     pub fn hover(&self, selectables: &[Vec<(usize, usize)>]) -> Option<(usize, usize)> {
         match self {
             Self::Hover(x, y) if !selectables.is_empty() => {
@@ -223,13 +374,48 @@ impl CursorState {
         Ok(())
     }
 
+    /// Move in the corresponding direction and report if the cursor moved, with `wrap` the
+    /// cursor goes to the other end of the row or column when blocked by an edge
+    pub fn try_move(
+        &mut self,
+        direction: FrameworkDirection,
+        selectables: &[Vec<(usize, usize)>],
+        wrap: bool,
+    ) -> Result<MoveResult, FrameworkError> {
+        let before = *self;
+        self.r#move(direction, selectables)?;
+
+        if *self != before {
+            return Ok(MoveResult::Moved);
+        }
+
+        let (x, y) = match self {
+            Self::Hover(x, y) if wrap => (*x, *y),
+            _ => return Ok(MoveResult::Blocked),
+        };
+
+        *self = match direction {
+            FrameworkDirection::Up => Self::Hover(x, usize::MAX),
+            FrameworkDirection::Down => Self::Hover(x, 0),
+            FrameworkDirection::Left => Self::Hover(usize::MAX, y),
+            FrameworkDirection::Right => Self::Hover(0, y),
+        };
+        self.move_check(selectables);
+
+        if *self == before {
+            Ok(MoveResult::Blocked)
+        } else {
+            Ok(MoveResult::Wrapped)
+        }
+    }
+
     fn move_check(&mut self, selectables: &[Vec<(usize, usize)>]) {
+        if selectables.is_empty() {
+            *self = Self::None;
+            return;
+        }
+
         if let Self::Hover(x, y) = self {
-            if selectables.is_empty() {
-                *x = 0;
-                *y = 0;
-                return;
-            }
             let y_max = selectables.len() - 1;
             if *y > y_max {
                 *y = y_max;
@@ -293,8 +479,102 @@ impl CursorState {
     }
 }
 
+impl CursorState {
+    /// Move to the next selectable item in `tab_order`, wraps around to the first item
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(
+        &mut self,
+        tab_order: TabOrder,
+        selectables: &[Vec<(usize, usize)>],
+    ) -> Result<(), FrameworkError> {
+        self.tab(tab_order, selectables, true)
+    }
+
+    /// Move to the previous selectable item in `tab_order`, wraps around to the last item
+    pub fn prev(
+        &mut self,
+        tab_order: TabOrder,
+        selectables: &[Vec<(usize, usize)>],
+    ) -> Result<(), FrameworkError> {
+        self.tab(tab_order, selectables, false)
+    }
+
+    fn tab(
+        &mut self,
+        tab_order: TabOrder,
+        selectables: &[Vec<(usize, usize)>],
+        forward: bool,
+    ) -> Result<(), FrameworkError> {
+        if self.is_selected() {
+            return Err(FrameworkError::MoveSelected);
+        }
+
+        let order = tab_order.order(selectables);
+        if order.is_empty() {
+            return Ok(());
+        }
+
+        let current = match self {
+            Self::Hover(x, y) => order.iter().position(|location| *location == (*x, *y)),
+            _ => None,
+        };
+
+        let next = match (current, forward) {
+            (Some(index), true) => (index + 1) % order.len(),
+            (Some(index), false) => (index + order.len() - 1) % order.len(),
+            (None, true) => 0,
+            (None, false) => order.len() - 1,
+        };
+
+        *self = Self::to_hover(order[next]);
+        Ok(())
+    }
+}
+
+/// The order which `next()` and `prev()` goes through selectable items
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum TabOrder {
+    /// Left to right, then top to bottom
+    #[default]
+    RowMajor,
+    /// Top to bottom, then left to right, by the columns in `State`. Rows without an item in the
+    /// current column are skipped
+    ColumnMajor,
+}
+
+impl TabOrder {
+    /// Locations in `selectables` (not `State.0`) in the tab order
+    pub fn order(&self, selectables: &[Vec<(usize, usize)>]) -> Vec<(usize, usize)> {
+        match self {
+            Self::RowMajor => selectables
+                .iter()
+                .enumerate()
+                .flat_map(|(y, row)| (0..row.len()).map(move |x| (x, y)))
+                .collect(),
+            Self::ColumnMajor => {
+                let mut columns = selectables
+                    .iter()
+                    .flatten()
+                    .map(|(x, _)| *x)
+                    .collect::<Vec<_>>();
+                columns.sort_unstable();
+                columns.dedup();
+
+                columns
+                    .into_iter()
+                    .flat_map(|column| {
+                        selectables.iter().enumerate().filter_map(move |(y, row)| {
+                            row.iter().position(|(x, _)| *x == column).map(|x| (x, y))
+                        })
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
 /// Used to represent direction in this crate
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum FrameworkDirection {
     Up,
     Down,
@@ -302,11 +582,24 @@ pub enum FrameworkDirection {
     Right,
 }
 
+/// What happened to the cursor after `try_move()`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MoveResult {
+    /// The cursor moved to another item
+    Moved,
+    /// There is nothing in that direction, the cursor did not move
+    Blocked,
+    /// The cursor wrapped around to the other end
+    Wrapped,
+}
+
 /// Passed into the `FrameworkItem` trait functions for info of the item
 #[derive(Clone, Copy)]
 pub struct ItemInfo {
     pub selected: bool,
     pub hover: bool,
+    /// If the item is in `Framework::marked_range()`
+    pub in_range: bool,
     pub x: usize,
     pub y: usize,
 }
@@ -0,0 +1,52 @@
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+    Frame,
+};
+
+/// Where a toast is placed in the frame, always centered horizontally
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ToastAnchor {
+    Top,
+    Center,
+    Bottom,
+}
+
+/// A short message drawn over everything else, set with `Framework::set_toast()`
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Toast {
+    pub text: String,
+    pub style: Style,
+    pub anchor: ToastAnchor,
+}
+
+impl Toast {
+    /// Area of the box (including borders) when drawn in `area`
+    pub fn area(&self, area: Rect) -> Rect {
+        let width = (Line::from(self.text.as_str()).width() as u16)
+            .saturating_add(2)
+            .min(area.width);
+        let height = 3.min(area.height);
+
+        let y = match self.anchor {
+            ToastAnchor::Top => area.y,
+            ToastAnchor::Center => area.y + (area.height - height) / 2,
+            ToastAnchor::Bottom => area.bottom() - height,
+        };
+
+        Rect::new(area.x + (area.width - width) / 2, y, width, height)
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let area = self.area(area);
+        let buf = frame.buffer_mut();
+
+        Clear.render(area, buf);
+        Paragraph::new(self.text.as_str())
+            .style(self.style)
+            .block(Block::default().borders(Borders::ALL).style(self.style))
+            .render(area, buf);
+    }
+}
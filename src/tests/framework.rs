@@ -0,0 +1,1265 @@
+use std::{any::Any, cell::Cell, error::Error, rc::Rc};
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    backend::TestBackend,
+    layout::{Constraint, Rect},
+    style::Style,
+    Frame, Terminal,
+};
+use typemap::Key;
+
+use crate::framework::{
+    CursorState, EventOutcome, Framework, FrameworkClean, FrameworkDirection, FrameworkError,
+    FrameworkEvent, FrameworkItem, ItemInfo, MoveResult, NavAction, NavKeys, Row, RowAlign,
+    RowItem, State, TabOrder, ToastAnchor,
+};
+
+#[derive(Clone)]
+struct Item {
+    selectable: bool,
+}
+
+impl FrameworkItem for Item {
+    fn selectable(&self) -> bool {
+        self.selectable
+    }
+
+    fn on_framework_focus(&mut self, framework: &mut FrameworkClean, focused: bool) {
+        framework
+            .data
+            .global
+            .entry::<FocusEvents>()
+            .or_insert_with(Vec::new)
+            .push(focused);
+    }
+
+    fn hover_key_event(
+        &mut self,
+        framework: &mut FrameworkClean,
+        key: KeyEvent,
+        info: ItemInfo,
+    ) -> Result<bool, Box<dyn Error>> {
+        if key.code != KeyCode::Char(' ') {
+            return Ok(false);
+        }
+
+        framework
+            .data
+            .global
+            .entry::<Toggled>()
+            .or_insert_with(Vec::new)
+            .push((info.x, info.y));
+        Ok(true)
+    }
+}
+
+struct FocusEvents;
+
+struct Toggled;
+
+impl Key for Toggled {
+    type Value = Vec<(usize, usize)>;
+}
+
+impl Key for FocusEvents {
+    type Value = Vec<bool>;
+}
+
+/// Fills its area with `symbol` when rendered
+#[derive(Clone)]
+struct Fill {
+    symbol: char,
+    fullscreen: bool,
+}
+
+impl FrameworkItem for Fill {
+    fn wants_fullscreen(&self) -> bool {
+        self.fullscreen
+    }
+
+    fn render(
+        &mut self,
+        frame: &mut Frame,
+        _framework: &mut FrameworkClean,
+        area: Rect,
+        popup_render: bool,
+        _info: ItemInfo,
+    ) {
+        if popup_render {
+            return;
+        }
+
+        let line = self.symbol.to_string().repeat(area.width as usize);
+        for y in area.top()..area.bottom() {
+            frame
+                .buffer_mut()
+                .set_string(area.x, y, &line, Style::default());
+        }
+    }
+}
+
+fn draw(framework: &mut Framework, width: u16, height: u16) -> Vec<String> {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal.draw(|frame| framework.render(frame)).unwrap();
+
+    let buffer = terminal.backend().buffer();
+    (0..height)
+        .map(|y| {
+            (0..width)
+                .map(|x| buffer[(x, y)].symbol())
+                .collect::<String>()
+        })
+        .collect()
+}
+
+fn row(selectables: &[bool]) -> Row {
+    Row {
+        items: selectables
+            .iter()
+            .map(|selectable| RowItem {
+                item: Box::new(Item {
+                    selectable: *selectable,
+                }),
+                width: Constraint::Length(10),
+                z: 0,
+            })
+            .collect(),
+        centered: false,
+        align: RowAlign::Start,
+        height: Constraint::Length(3),
+    }
+}
+
+#[test]
+fn selectables_shape() {
+    {
+        let framework = Framework::new(State(vec![
+            row(&[true, true, true]),
+            row(&[false, false]),
+            row(&[true, false, true, true]),
+        ]));
+
+        assert_eq!(2, framework.rows_selectable());
+        assert_eq!(Some(3), framework.cols_selectable(0));
+        assert_eq!(Some(3), framework.cols_selectable(1));
+        assert_eq!(None, framework.cols_selectable(2));
+    }
+
+    {
+        let framework = Framework::new(State(Vec::new()));
+
+        assert_eq!(0, framework.rows_selectable());
+        assert_eq!(None, framework.cols_selectable(0));
+    }
+}
+
+#[test]
+fn load_with_progress() {
+    let mut framework = Framework::new(State(vec![
+        row(&[true, false]),
+        row(&[false]),
+        row(&[true, true, true]),
+    ]));
+
+    let mut progress = Vec::new();
+    framework
+        .load_with_progress(|done, total| progress.push((done, total)))
+        .unwrap();
+
+    assert_eq!(
+        vec![(1, 6), (2, 6), (3, 6), (4, 6), (5, 6), (6, 6)],
+        progress
+    );
+}
+
+#[test]
+fn fullscreen() {
+    let mut framework = Framework::new(State(vec![Row {
+        items: vec![
+            RowItem {
+                item: Box::new(Fill {
+                    symbol: 'a',
+                    fullscreen: false,
+                }),
+                width: Constraint::Length(2),
+                z: 0,
+            },
+            RowItem {
+                item: Box::new(Fill {
+                    symbol: 'b',
+                    fullscreen: true,
+                }),
+                width: Constraint::Length(2),
+                z: 0,
+            },
+        ],
+        centered: false,
+        align: RowAlign::Start,
+        height: Constraint::Length(1),
+    }]));
+
+    assert_eq!(vec!["aabb", "    "], draw(&mut framework, 4, 2));
+
+    framework.cursor = CursorState::Hover(1, 0);
+    framework.select().unwrap();
+    assert_eq!(vec!["bbbb", "bbbb"], draw(&mut framework, 4, 2));
+
+    framework.deselect().unwrap();
+    assert_eq!(vec!["aabb", "    "], draw(&mut framework, 4, 2));
+}
+
+#[test]
+fn focus() {
+    let mut framework = Framework::new(State(vec![row(&[true, false]), row(&[true])]));
+    assert!(framework.focused);
+
+    framework.set_focused(true);
+    assert_eq!(None, framework.data.global.get::<FocusEvents>());
+
+    framework.set_focused(false);
+    assert_eq!(
+        Some(&vec![false, false, false]),
+        framework.data.global.get::<FocusEvents>()
+    );
+
+    framework.set_focused(false);
+    framework.set_focused(true);
+    assert!(framework.focused);
+    assert_eq!(
+        Some(&vec![false, false, false, true, true, true]),
+        framework.data.global.get::<FocusEvents>()
+    );
+}
+
+#[test]
+fn tab_order() {
+    let mut framework = Framework::new(State(vec![
+        row(&[true, true, true]),
+        row(&[true, false, true]),
+    ]));
+
+    let traverse = |framework: &mut Framework| {
+        (0..6)
+            .map(|_| {
+                framework.next().unwrap();
+                framework.cursor.hover(&framework.selectables).unwrap()
+            })
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        vec![(0, 0), (1, 0), (2, 0), (0, 1), (2, 1), (0, 0)],
+        traverse(&mut framework)
+    );
+
+    framework.cursor = CursorState::None;
+    framework.set_tab_order(TabOrder::ColumnMajor);
+    assert_eq!(
+        vec![(0, 0), (0, 1), (1, 0), (2, 0), (2, 1), (0, 0)],
+        traverse(&mut framework)
+    );
+
+    framework.prev().unwrap();
+    assert_eq!(Some((2, 1)), framework.cursor.hover(&framework.selectables));
+}
+
+#[test]
+fn cursor_checked() {
+    let framework = Framework::new(State(vec![row(&[true, true, true]), row(&[true])]));
+    let selectables = &framework.selectables;
+
+    assert_eq!(
+        Some(CursorState::Hover(2, 0)),
+        CursorState::hover_checked(2, 0, selectables)
+    );
+    assert_eq!(
+        Some(CursorState::Selected(0, 1)),
+        CursorState::selected_checked(0, 1, selectables)
+    );
+    assert_eq!(None, CursorState::hover_checked(1, 1, selectables));
+    assert_eq!(None, CursorState::hover_checked(0, 2, selectables));
+
+    let mut cursor = CursorState::Hover(5, 0);
+    cursor.clamp(selectables);
+    assert_eq!(CursorState::Hover(2, 0), cursor);
+
+    let mut cursor = CursorState::Selected(2, 7);
+    cursor.clamp(selectables);
+    assert_eq!(CursorState::Selected(0, 1), cursor);
+
+    let mut cursor = CursorState::Hover(1, 1);
+    cursor.clamp(&[]);
+    assert_eq!(CursorState::None, cursor);
+}
+
+#[test]
+fn flat_index() {
+    let mut framework = Framework::new(State(vec![
+        row(&[true, false, true]),
+        row(&[false]),
+        row(&[true, true, false, true]),
+    ]));
+
+    assert_eq!(None, framework.current_index());
+    assert_eq!(Some((0, 0)), framework.coords_of_index(0));
+    assert_eq!(Some((2, 0)), framework.coords_of_index(1));
+    assert_eq!(Some((0, 2)), framework.coords_of_index(2));
+    assert_eq!(Some((3, 2)), framework.coords_of_index(4));
+    assert_eq!(None, framework.coords_of_index(5));
+
+    framework.cursor = CursorState::Hover(2, 1);
+    assert_eq!(Some(4), framework.current_index());
+}
+
+#[test]
+fn z_index() {
+    let fill = |symbol, z| RowItem {
+        item: Box::new(Fill {
+            symbol,
+            fullscreen: false,
+        }),
+        width: Constraint::Length(2),
+        z,
+    };
+    let mut framework = Framework::new(State(vec![Row {
+        items: vec![fill('a', 1), fill('b', 0), fill('c', 0)],
+        centered: false,
+        align: RowAlign::Start,
+        height: Constraint::Length(1),
+    }]));
+
+    let mut terminal = Terminal::new(TestBackend::new(3, 1)).unwrap();
+    terminal
+        .draw(|frame| {
+            let area = frame.area();
+            framework.render_raw(frame, &[vec![area, area, area]], None, None, false);
+        })
+        .unwrap();
+
+    let buffer = terminal.backend().buffer();
+    assert_eq!(
+        "aaa",
+        (0..3).map(|x| buffer[(x, 0)].symbol()).collect::<String>()
+    );
+}
+
+#[test]
+fn debug_layout() {
+    let mut framework = Framework::new(State(vec![
+        row(&[true, false, true]),
+        row(&[false]),
+        row(&[true, true]),
+    ]));
+
+    assert_eq!(
+        "cursor: none\n0: S . S\n1: .\n2: S S",
+        framework.debug_layout()
+    );
+
+    framework.cursor = CursorState::Hover(1, 0);
+    assert_eq!(
+        "cursor: hover (2, 0)\n0: S . H\n1: .\n2: S S",
+        framework.debug_layout()
+    );
+
+    framework.cursor = CursorState::Selected(0, 1);
+    assert_eq!(
+        "cursor: selected (0, 2)\n0: S . S\n1: .\n2: * S",
+        framework.debug_layout()
+    );
+}
+
+#[test]
+fn hover_key_input() {
+    let mut framework = Framework::new(State(vec![row(&[false, true, true])]));
+    let space = KeyEvent::from(KeyCode::Char(' '));
+
+    assert!(!framework.hover_key_input(space).unwrap());
+
+    framework.cursor = CursorState::Hover(1, 0);
+    assert!(framework.hover_key_input(space).unwrap());
+    assert!(!framework
+        .hover_key_input(KeyEvent::from(KeyCode::Enter))
+        .unwrap());
+    assert_eq!(Some(&vec![(2, 0)]), framework.data.global.get::<Toggled>());
+
+    framework.select().unwrap();
+    assert!(!framework.hover_key_input(space).unwrap());
+}
+
+#[test]
+fn empty() {
+    let mut framework = Framework::empty();
+
+    assert_eq!(vec!["    ", "    "], draw(&mut framework, 4, 2));
+
+    [
+        FrameworkDirection::Up,
+        FrameworkDirection::Down,
+        FrameworkDirection::Left,
+        FrameworkDirection::Right,
+    ]
+    .into_iter()
+    .for_each(|direction| framework.r#move(direction).unwrap());
+    framework.next().unwrap();
+    framework.prev().unwrap();
+    assert!(framework.is_none());
+
+    assert!(framework.select().is_err());
+    assert!(framework.deselect().is_err());
+    framework.key_input(KeyEvent::from(KeyCode::Enter)).unwrap();
+    assert!(!framework
+        .hover_key_input(KeyEvent::from(KeyCode::Enter))
+        .unwrap());
+    assert!(framework.mouse_event(1, 1));
+    assert!(framework.is_none());
+
+    framework.load().unwrap();
+    framework.load_with_progress(|_, _| ()).unwrap();
+    framework.load_only_multiple(&[]);
+    framework.set_focused(false);
+
+    assert_eq!(0, framework.rows_selectable());
+    assert_eq!(None, framework.current_index());
+    assert_eq!(None, framework.coords_of_index(0));
+    assert_eq!("cursor: none", framework.debug_layout());
+
+    framework.push_history();
+    framework.revert_last_history().unwrap();
+    assert!(framework.revert_last_history().is_err());
+}
+
+#[test]
+fn has_selectables() {
+    let framework = Framework::new(State(vec![row(&[false, false]), row(&[false])]));
+    assert!(framework.is_none());
+    assert!(!framework.has_selectables());
+
+    let framework = Framework::new(State(vec![row(&[false, true])]));
+    assert!(framework.is_none());
+    assert!(framework.has_selectables());
+}
+
+#[test]
+fn vertical_scroll() {
+    let fill = |symbol| Row {
+        items: vec![RowItem {
+            item: Box::new(Fill {
+                symbol,
+                fullscreen: false,
+            }),
+            width: Constraint::Length(2),
+            z: 0,
+        }],
+        centered: false,
+        align: RowAlign::Start,
+        height: Constraint::Length(2),
+    };
+    let mut framework = Framework::new(State(vec![fill('a'), fill('b'), fill('c')]));
+
+    assert_eq!(6, framework.state.content_height());
+    framework.set_vertical_scroll(3);
+    assert_eq!(vec!["b", "c", "c", " "], draw(&mut framework, 1, 4));
+
+    framework.push_history();
+    framework.set_state(State(vec![fill('d')]));
+    framework.set_vertical_scroll(0);
+
+    framework.revert_last_history().unwrap();
+    assert_eq!(3, framework.vertical_scroll);
+    assert_eq!(vec!["b", "c", "c", " "], draw(&mut framework, 1, 4));
+}
+
+#[test]
+fn row_align() {
+    let xs = |align, centered| {
+        let mut row = row(&[true, true, true]);
+        row.items
+            .iter_mut()
+            .for_each(|row_item| row_item.width = Constraint::Length(2));
+        row.align = align;
+        row.centered = centered;
+
+        State(vec![row]).get_chunks(Rect::new(0, 0, 13, 3))[0]
+            .iter()
+            .map(|chunk| chunk.x)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(vec![0, 2, 4], xs(RowAlign::Start, false));
+    assert_eq!(vec![3, 5, 7], xs(RowAlign::Center, false));
+    assert_eq!(vec![3, 5, 7], xs(RowAlign::Start, true));
+    assert_eq!(vec![7, 9, 11], xs(RowAlign::End, false));
+    assert_eq!(vec![0, 6, 11], xs(RowAlign::SpaceBetween, false));
+}
+
+#[test]
+fn empty_row_chunks() {
+    let chunks = State(vec![row(&[]), row(&[true])]).get_chunks(Rect::new(0, 0, 13, 6));
+    assert_eq!(vec![0, 1], chunks.iter().map(Vec::len).collect::<Vec<_>>());
+}
+
+#[test]
+fn item_at_point() {
+    let mut framework = Framework::new(State(vec![row(&[true, false]), row(&[false])]));
+    assert_eq!(None, framework.item_at_point(0, 0));
+
+    framework.frame_area = Some(Rect::new(0, 0, 30, 10));
+    assert_eq!(Some((0, 0)), framework.item_at_point(3, 1));
+    assert_eq!(Some((1, 0)), framework.item_at_point(15, 2));
+    assert_eq!(Some((0, 1)), framework.item_at_point(9, 3));
+    assert_eq!(None, framework.item_at_point(25, 1));
+    assert_eq!(None, framework.item_at_point(3, 8));
+    assert!(framework.is_none());
+}
+
+#[test]
+fn state_grid() {
+    let item = |selectable| Box::new(Item { selectable }) as Box<dyn FrameworkItem>;
+    let state = State::grid(
+        vec![vec![item(true), item(false)], vec![item(true), item(true)]],
+        Constraint::Length(4),
+        Constraint::Length(2),
+        true,
+    );
+
+    assert_eq!(2, state.0.len());
+    assert!(state.0.iter().all(|row| row.items.len() == 2));
+    assert_eq!(
+        vec![vec![(0, 0)], vec![(0, 1), (1, 1)]],
+        state.selectables()
+    );
+    assert_eq!(
+        vec![
+            vec![Rect::new(6, 0, 4, 2), Rect::new(10, 0, 4, 2)],
+            vec![Rect::new(6, 2, 4, 2), Rect::new(10, 2, 4, 2)]
+        ],
+        state.get_chunks(Rect::new(0, 0, 20, 10))
+    );
+}
+
+#[test]
+fn on_screen_change() {
+    let changes = Rc::new(Cell::new(0));
+    let mut framework = Framework::new(State(vec![row(&[true, false])]));
+    framework.set_on_screen_change({
+        let changes = changes.clone();
+        move |_| changes.set(changes.get() + 1)
+    });
+
+    framework.state.0[0].items[1].z = 1;
+    framework.set_vertical_scroll(2);
+    assert_eq!(0, changes.get());
+
+    framework.push_history();
+    framework.set_state(State(vec![row(&[true]), row(&[true])]));
+    assert_eq!(1, changes.get());
+
+    framework.revert_last_history().unwrap();
+    assert_eq!(2, changes.get());
+    assert!(framework.clone().on_screen_change.is_none());
+}
+
+#[test]
+fn try_move() {
+    let mut framework = Framework::new(State(vec![row(&[true, true]), row(&[true])]));
+
+    assert_eq!(
+        MoveResult::Moved,
+        framework.try_move(FrameworkDirection::Up).unwrap()
+    );
+    assert_eq!(CursorState::Hover(0, 0), framework.cursor);
+    assert_eq!(
+        MoveResult::Blocked,
+        framework.try_move(FrameworkDirection::Left).unwrap()
+    );
+    assert_eq!(
+        MoveResult::Blocked,
+        framework.try_move(FrameworkDirection::Up).unwrap()
+    );
+    assert_eq!(CursorState::Hover(0, 0), framework.cursor);
+
+    framework.set_wrap(true);
+    assert_eq!(
+        MoveResult::Wrapped,
+        framework.try_move(FrameworkDirection::Left).unwrap()
+    );
+    assert_eq!(CursorState::Hover(1, 0), framework.cursor);
+    assert_eq!(
+        MoveResult::Wrapped,
+        framework.try_move(FrameworkDirection::Up).unwrap()
+    );
+    assert_eq!(CursorState::Hover(0, 1), framework.cursor);
+    assert_eq!(
+        MoveResult::Blocked,
+        framework.try_move(FrameworkDirection::Right).unwrap()
+    );
+
+    framework.select().unwrap();
+    assert!(framework.try_move(FrameworkDirection::Down).is_err());
+}
+
+#[test]
+fn clone_state() {
+    let framework = Framework::new(State(vec![row(&[true, false])]));
+
+    let mut state = framework.clone_state();
+    state.0[0].items[1].z = 1;
+    state.0.push(row(&[true]));
+
+    assert_eq!(1, framework.state.0.len());
+    assert_eq!(0, framework.state.0[0].items[1].z);
+
+    let mut preview = State(vec![Row {
+        items: vec![RowItem {
+            item: Box::new(Fill {
+                symbol: '#',
+                fullscreen: false,
+            }),
+            width: Constraint::Length(2),
+            z: 0,
+        }],
+        centered: false,
+        align: RowAlign::Start,
+        height: Constraint::Length(1),
+    }]);
+    let mut terminal = Terminal::new(TestBackend::new(4, 2)).unwrap();
+    terminal
+        .draw(|frame| framework.preview_render(frame, Rect::new(1, 1, 3, 1), &mut preview))
+        .unwrap();
+
+    let buffer = terminal.backend().buffer();
+    let line = (0..4).map(|x| buffer[(x, 1)].symbol()).collect::<String>();
+    assert_eq!(" ## ", line);
+}
+
+#[test]
+fn lock_navigation() {
+    let mut framework = Framework::new(State(vec![row(&[true, true])]));
+    framework.r#move(FrameworkDirection::Left).unwrap();
+
+    framework.lock_navigation(true);
+    assert!(matches!(
+        framework.r#move(FrameworkDirection::Right),
+        Err(FrameworkError::NavigationLocked)
+    ));
+    assert!(matches!(
+        framework.next(),
+        Err(FrameworkError::NavigationLocked)
+    ));
+    assert!(matches!(
+        framework.prev(),
+        Err(FrameworkError::NavigationLocked)
+    ));
+    assert_eq!(CursorState::Hover(0, 0), framework.cursor);
+
+    framework.lock_navigation(false);
+    framework.r#move(FrameworkDirection::Right).unwrap();
+    assert_eq!(CursorState::Hover(1, 0), framework.cursor);
+}
+
+#[test]
+fn align_vertical_by_pixel() {
+    let mut framework = Framework::new(State(vec![
+        row(&[false, false, true]),
+        row(&[true, true, true]),
+    ]));
+    draw(&mut framework, 40, 10);
+
+    framework.r#move(FrameworkDirection::Up).unwrap();
+    framework.r#move(FrameworkDirection::Down).unwrap();
+    assert_eq!(CursorState::Hover(0, 1), framework.cursor);
+
+    framework.r#move(FrameworkDirection::Up).unwrap();
+    framework.set_align_vertical_by_pixel(true);
+    framework.r#move(FrameworkDirection::Down).unwrap();
+    assert_eq!(CursorState::Hover(2, 1), framework.cursor);
+}
+
+#[test]
+fn nav_keys() {
+    let mut framework = Framework::new(State(vec![row(&[true]), row(&[true])]));
+    framework.set_nav_keys(
+        NavKeys::empty()
+            .bind(
+                KeyCode::Char('n'),
+                KeyModifiers::CONTROL,
+                NavAction::Move(FrameworkDirection::Down),
+            )
+            .bind(
+                KeyCode::Char('p'),
+                KeyModifiers::CONTROL,
+                NavAction::Move(FrameworkDirection::Up),
+            )
+            .bind(KeyCode::Char('j'), KeyModifiers::CONTROL, NavAction::Select)
+            .bind(
+                KeyCode::Char('g'),
+                KeyModifiers::CONTROL,
+                NavAction::Deselect,
+            ),
+    );
+    let ctrl = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL);
+
+    assert!(!framework.handle_key(KeyEvent::from(KeyCode::Down)).unwrap());
+    assert!(framework.cursor.is_none());
+
+    assert!(framework.handle_key(ctrl('p')).unwrap());
+    assert!(framework.handle_key(ctrl('n')).unwrap());
+    assert_eq!(CursorState::Hover(0, 1), framework.cursor);
+    assert!(framework.handle_key(ctrl('p')).unwrap());
+    assert_eq!(CursorState::Hover(0, 0), framework.cursor);
+
+    assert!(framework.handle_key(ctrl('j')).unwrap());
+    assert_eq!(CursorState::Selected(0, 0), framework.cursor);
+    assert!(framework.handle_key(ctrl('n')).unwrap());
+    assert_eq!(CursorState::Selected(0, 0), framework.cursor);
+    assert!(framework.handle_key(ctrl('g')).unwrap());
+    assert_eq!(CursorState::Hover(0, 0), framework.cursor);
+}
+
+#[test]
+fn default() {
+    #[derive(Default)]
+    struct App {
+        framework: Framework,
+    }
+
+    let mut app = App::default();
+    assert!(app.framework.state.0.is_empty());
+    assert!(app.framework.history.is_empty());
+    assert!(app.framework.is_none());
+
+    assert_eq!(vec!["    ", "    "], draw(&mut app.framework, 4, 2));
+
+    app.framework.set_wrap(true);
+    app.framework.set_align_vertical_by_pixel(true);
+    assert_eq!(
+        MoveResult::Blocked,
+        app.framework.try_move(FrameworkDirection::Down).unwrap()
+    );
+    assert!(app
+        .framework
+        .handle_key(KeyEvent::from(KeyCode::Enter))
+        .is_ok());
+    assert!(app.framework.is_none());
+}
+
+#[test]
+fn dispatch() {
+    let mut framework = Framework::new(State(vec![row(&[false, true]), row(&[true])]));
+    let key = |code| Event::Key(KeyEvent::from(code));
+
+    assert_eq!(
+        EventOutcome::Navigation(Some((1, 0))),
+        framework.dispatch(key(KeyCode::Up)).unwrap()
+    );
+    assert_eq!(
+        EventOutcome::Item((1, 0)),
+        framework.dispatch(key(KeyCode::Char(' '))).unwrap()
+    );
+    assert_eq!(
+        EventOutcome::Ignored,
+        framework.dispatch(key(KeyCode::Char('a'))).unwrap()
+    );
+    assert_eq!(
+        EventOutcome::Navigation(Some((1, 0))),
+        framework.dispatch(key(KeyCode::Enter)).unwrap()
+    );
+    assert_eq!(
+        EventOutcome::Item((1, 0)),
+        framework.dispatch(key(KeyCode::Char('a'))).unwrap()
+    );
+    assert_eq!(
+        EventOutcome::Navigation(Some((1, 0))),
+        framework.dispatch(key(KeyCode::Esc)).unwrap()
+    );
+    assert_eq!(
+        EventOutcome::Navigation(Some((0, 1))),
+        framework.dispatch(key(KeyCode::Down)).unwrap()
+    );
+}
+
+#[test]
+fn item_meta() {
+    struct ItemId;
+
+    impl Key for ItemId {
+        type Value = String;
+    }
+
+    let mut framework = Framework::new(State(vec![row(&[true, true])]));
+    framework.set_item_meta::<ItemId>(1, 0, String::from("save-button"));
+
+    assert_eq!(
+        Some(&String::from("save-button")),
+        framework.get_item_meta::<ItemId>(1, 0)
+    );
+    assert_eq!(None, framework.get_item_meta::<ItemId>(0, 0));
+
+    let (frameworkclean, _) = framework.split_clean();
+    assert_eq!(
+        Some(&String::from("save-button")),
+        frameworkclean
+            .item_meta
+            .get(&(1, 0))
+            .and_then(|meta| meta.get::<ItemId>())
+    );
+}
+
+#[test]
+fn builder() {
+    let framework = Framework::builder()
+        .state(State(vec![row(&[true, false]), row(&[true])]))
+        .tab_order(TabOrder::ColumnMajor)
+        .wrap(true)
+        .nav_locked(true)
+        .align_vertical_by_pixel(true)
+        .vertical_scroll(2)
+        .focused(false)
+        .build();
+
+    assert_eq!(vec![vec![(0, 0)], vec![(0, 1)]], framework.selectables);
+    assert_eq!(TabOrder::ColumnMajor, framework.tab_order);
+    assert!(framework.wrap);
+    assert!(framework.nav_locked);
+    assert!(framework.align_vertical_by_pixel);
+    assert_eq!(2, framework.vertical_scroll);
+    assert!(!framework.focused);
+    assert!(framework.cursor.is_none());
+}
+
+#[test]
+fn request_relayout() {
+    #[derive(Clone)]
+    struct Collapsible {
+        expanded: bool,
+    }
+
+    impl FrameworkItem for Collapsible {
+        fn selectable(&self) -> bool {
+            self.expanded
+        }
+
+        fn key_event(
+            &mut self,
+            framework: &mut FrameworkClean,
+            key: KeyEvent,
+            _info: ItemInfo,
+        ) -> Result<(), Box<dyn Error>> {
+            if key.code == KeyCode::Char('c') {
+                self.expanded = false;
+                framework.request_relayout();
+            }
+            Ok(())
+        }
+    }
+
+    let mut framework = Framework::new(State(vec![
+        Row {
+            items: vec![RowItem {
+                item: Box::new(Collapsible { expanded: true }),
+                width: Constraint::Length(10),
+                z: 0,
+            }],
+            centered: false,
+            align: RowAlign::Start,
+            height: Constraint::Length(3),
+        },
+        row(&[true]),
+    ]));
+    framework.r#move(FrameworkDirection::Up).unwrap();
+    framework.select().unwrap();
+
+    framework
+        .key_input(KeyEvent::from(KeyCode::Char('x')))
+        .unwrap();
+    assert_eq!(vec![vec![(0, 0)], vec![(0, 1)]], framework.selectables);
+
+    framework
+        .key_input(KeyEvent::from(KeyCode::Char('c')))
+        .unwrap();
+    assert_eq!(vec![vec![(0, 1)]], framework.selectables);
+    assert!(!framework.relayout_requested);
+    assert_eq!(CursorState::Selected(0, 0), framework.cursor);
+}
+
+#[test]
+fn drain_events() {
+    let mut framework = Framework::new(State(vec![row(&[true, true]), row(&[true])]));
+    assert!(framework.drain_events().is_empty());
+
+    framework.r#move(FrameworkDirection::Up).unwrap();
+    framework.r#move(FrameworkDirection::Right).unwrap();
+    framework.select().unwrap();
+    framework.deselect().unwrap();
+
+    assert_eq!(
+        vec![
+            FrameworkEvent::HoverChanged(Some((0, 0))),
+            FrameworkEvent::HoverChanged(Some((1, 0))),
+            FrameworkEvent::Selected((1, 0)),
+            FrameworkEvent::Deselected((1, 0)),
+        ],
+        framework.drain_events()
+    );
+    assert!(framework.drain_events().is_empty());
+}
+
+#[test]
+fn toast() {
+    let mut framework = Framework::new(State(vec![Row {
+        items: vec![RowItem {
+            item: Box::new(Fill {
+                symbol: '#',
+                fullscreen: false,
+            }),
+            width: Constraint::Length(12),
+            z: 0,
+        }],
+        centered: false,
+        align: RowAlign::Start,
+        height: Constraint::Length(5),
+    }]));
+
+    framework.set_toast(String::from("Saved"), Style::default(), ToastAnchor::Bottom);
+    assert_eq!(
+        vec![
+            "############",
+            "############",
+            "##┌─────┐###",
+            "##│Saved│###",
+            "##└─────┘###",
+        ],
+        draw(&mut framework, 12, 5)
+    );
+
+    framework.set_toast(String::from("Hi"), Style::default(), ToastAnchor::Top);
+    assert_eq!(
+        vec![
+            "####┌──┐####",
+            "####│Hi│####",
+            "####└──┘####",
+            "############",
+            "############",
+        ],
+        draw(&mut framework, 12, 5)
+    );
+
+    framework.clear_toast();
+    assert_eq!(vec!["############"; 5], draw(&mut framework, 12, 5));
+}
+
+#[test]
+fn marked_range() {
+    let mut framework = Framework::new(State(vec![
+        row(&[true, true, true]),
+        row(&[true, true, true]),
+        row(&[true, true, true]),
+    ]));
+    framework.r#move(FrameworkDirection::Up).unwrap();
+    assert!(framework.marked_range().is_empty());
+
+    framework.r#move(FrameworkDirection::Right).unwrap();
+    framework.set_mark();
+    framework.r#move(FrameworkDirection::Down).unwrap();
+    framework.r#move(FrameworkDirection::Right).unwrap();
+    assert_eq!(
+        vec![(1, 0), (2, 0), (1, 1), (2, 1)],
+        framework.marked_range()
+    );
+
+    framework.r#move(FrameworkDirection::Left).unwrap();
+    framework.r#move(FrameworkDirection::Left).unwrap();
+    assert_eq!(
+        vec![(0, 0), (1, 0), (0, 1), (1, 1)],
+        framework.marked_range()
+    );
+
+    framework.clear_mark();
+    assert!(framework.marked_range().is_empty());
+}
+
+#[test]
+fn for_each_selectable() {
+    let mut framework = Framework::new(State(vec![
+        row(&[true, false, true]),
+        row(&[false]),
+        row(&[false, true]),
+    ]));
+
+    let mut visited = Vec::new();
+    framework.for_each_selectable(|item, x, y| {
+        assert!(item.selectable());
+        visited.push((x, y));
+    });
+    assert_eq!(vec![(0, 0), (2, 0), (1, 2)], visited);
+}
+
+#[test]
+fn resize_clamps_scroll() {
+    let mut framework = Framework::new(State(vec![
+        row(&[false]),
+        row(&[false]),
+        row(&[false]),
+        row(&[false]),
+    ]));
+    draw(&mut framework, 10, 4);
+    framework.set_vertical_scroll(10);
+    draw(&mut framework, 10, 4);
+    assert_eq!(10, framework.vertical_scroll);
+
+    draw(&mut framework, 10, 8);
+    assert_eq!(4, framework.vertical_scroll);
+
+    let mut framework = Framework::new(State(vec![
+        row(&[true]),
+        row(&[false]),
+        row(&[false]),
+        row(&[true]),
+    ]));
+    framework.r#move(FrameworkDirection::Down).unwrap();
+    draw(&mut framework, 10, 12);
+    framework.set_vertical_scroll(4);
+
+    draw(&mut framework, 10, 2);
+    assert_eq!(9, framework.vertical_scroll);
+}
+
+#[test]
+fn resize_clamps_scroll_layout_fn() {
+    let mut framework = Framework::new(State(vec![row(&[true]), row(&[true])]));
+    framework.set_layout_fn(|_, area| {
+        vec![
+            vec![Rect::new(area.x + 5, area.y, 4, area.height)],
+            vec![Rect::new(area.x, area.y, 4, area.height)],
+        ]
+    });
+    framework.r#move(FrameworkDirection::Down).unwrap();
+    draw(&mut framework, 10, 12);
+    framework.set_vertical_scroll(4);
+
+    draw(&mut framework, 10, 2);
+    assert_eq!(0, framework.vertical_scroll);
+}
+
+#[test]
+fn swap_items() {
+    let mut framework = Framework::new(State(vec![row(&[true, false]), row(&[false])]));
+    framework.state.0[0].items[0].width = Constraint::Length(5);
+
+    framework.swap_items((0, 0), (1, 0)).unwrap();
+    assert_eq!(vec![vec![(1, 0)]], framework.selectables);
+    assert_eq!(Constraint::Length(5), framework.state.0[0].items[1].width);
+
+    framework.swap_items((1, 0), (0, 1)).unwrap();
+    assert_eq!(vec![vec![(0, 1)]], framework.selectables);
+    assert_eq!(2, framework.state.0[0].items.len());
+    assert_eq!(1, framework.state.0[1].items.len());
+    assert_eq!(
+        Rect::new(0, 3, 5, 3),
+        framework.get_chunks(Rect::new(0, 0, 20, 6))[1][0]
+    );
+
+    assert!(matches!(
+        framework.swap_items((0, 0), (1, 1)),
+        Err(FrameworkError::NoSuchItem)
+    ));
+}
+
+#[test]
+fn layout_fn() {
+    let mut framework = Framework::new(State(vec![row(&[true]), row(&[true])]));
+    framework.set_layout_fn(|_, area| {
+        vec![
+            vec![Rect::new(area.x + 10, area.y + 5, 4, 2)],
+            vec![Rect::new(area.x, area.y, 4, 2)],
+        ]
+    });
+    framework.frame_area = Some(Rect::new(0, 0, 20, 10));
+
+    assert!(framework.mouse_event(1, 1));
+    assert_eq!(CursorState::Hover(0, 1), framework.cursor);
+    assert!(framework.mouse_event(12, 6));
+    assert_eq!(CursorState::Hover(0, 0), framework.cursor);
+    assert_eq!(Some((0, 0)), framework.item_at_point(13, 5));
+    assert_eq!(None, framework.item_at_point(5, 5));
+    assert!(framework.clone().layout_fn.is_some());
+}
+
+#[test]
+fn selectable_coords() {
+    let framework = Framework::new(State(vec![
+        row(&[false, true, true]),
+        row(&[]),
+        row(&[true]),
+        row(&[true, false, false, true]),
+    ]));
+
+    assert_eq!(
+        vec![(1, 0), (2, 0), (0, 2), (0, 3), (3, 3)],
+        framework.selectable_coords()
+    );
+}
+
+#[test]
+fn layout_dirty() {
+    let mut framework = Framework::new(State(vec![row(&[true, false])]));
+    assert!(framework.layout_dirty());
+
+    draw(&mut framework, 20, 3);
+    assert!(!framework.layout_dirty());
+    framework.r#move(FrameworkDirection::Up).unwrap();
+    assert!(!framework.layout_dirty());
+
+    framework.swap_items((0, 0), (1, 0)).unwrap();
+    assert!(framework.layout_dirty());
+    draw(&mut framework, 20, 3);
+    assert!(!framework.layout_dirty());
+
+    framework.set_state(State(vec![row(&[true]), row(&[true])]));
+    assert!(framework.layout_dirty());
+    draw(&mut framework, 20, 6);
+    assert!(!framework.layout_dirty());
+}
+
+#[test]
+fn repeat_move() {
+    let mut framework = Framework::new(State(vec![
+        row(&[true]),
+        row(&[false]),
+        row(&[true]),
+        row(&[true]),
+    ]));
+    framework.r#move(FrameworkDirection::Up).unwrap();
+
+    assert_eq!(
+        1,
+        framework.repeat_move(FrameworkDirection::Down, 1).unwrap()
+    );
+    assert_eq!(
+        1,
+        framework.repeat_move(FrameworkDirection::Down, 10).unwrap()
+    );
+    assert_eq!(CursorState::Hover(0, 2), framework.cursor);
+    assert_eq!(
+        0,
+        framework.repeat_move(FrameworkDirection::Down, 3).unwrap()
+    );
+
+    framework.select().unwrap();
+    assert!(matches!(
+        framework.repeat_move(FrameworkDirection::Up, 3),
+        Err(FrameworkError::MoveSelected)
+    ));
+}
+
+#[test]
+fn selected_item_mut() {
+    let fill = |symbol| RowItem {
+        item: Box::new(Fill {
+            symbol,
+            fullscreen: false,
+        }),
+        width: Constraint::Length(2),
+        z: 0,
+    };
+    let mut framework = Framework::new(State(vec![Row {
+        items: vec![fill('a'), fill('b')],
+        centered: false,
+        align: RowAlign::Start,
+        height: Constraint::Length(1),
+    }]));
+    assert!(framework.hovered_item_mut().is_none());
+
+    framework.r#move(FrameworkDirection::Up).unwrap();
+    assert!(framework.selected_item_mut().is_none());
+    *framework.hovered_item_mut().unwrap() = Box::new(Fill {
+        symbol: 'x',
+        fullscreen: false,
+    });
+
+    framework.r#move(FrameworkDirection::Right).unwrap();
+    framework.select().unwrap();
+    *framework.selected_item_mut().unwrap() = Box::new(Fill {
+        symbol: 'y',
+        fullscreen: false,
+    });
+    assert!(framework.hovered_item_mut().is_none());
+
+    assert_eq!(vec!["xxyy"], draw(&mut framework, 4, 1));
+}
+
+#[test]
+fn breadcrumb() {
+    let label = |state: &State| format!("{} rows", state.0.len());
+    let mut framework = Framework::new(State(vec![row(&[true])]));
+    assert_eq!(vec!["1 rows"], framework.breadcrumb(label));
+
+    framework.push_history();
+    framework.set_state(State(vec![row(&[true]), row(&[true])]));
+    framework.push_history();
+    framework.set_state(State(vec![row(&[true]), row(&[true]), row(&[true])]));
+
+    assert_eq!(
+        vec!["1 rows", "2 rows", "3 rows"],
+        framework.breadcrumb(label)
+    );
+}
+
+#[test]
+fn select_with() {
+    struct Received;
+
+    impl Key for Received {
+        type Value = String;
+    }
+
+    #[derive(Clone)]
+    struct Receiver;
+
+    impl FrameworkItem for Receiver {
+        fn select_with(&mut self, framework: &mut FrameworkClean, payload: &dyn Any) -> bool {
+            match payload.downcast_ref::<&str>() {
+                Some(payload) => {
+                    framework
+                        .data
+                        .global
+                        .insert::<Received>(payload.to_string());
+                    true
+                }
+                None => false,
+            }
+        }
+    }
+
+    let mut framework = Framework::new(State(vec![
+        Row {
+            items: vec![RowItem {
+                item: Box::new(Receiver),
+                width: Constraint::Length(10),
+                z: 0,
+            }],
+            centered: false,
+            align: RowAlign::Start,
+            height: Constraint::Length(3),
+        },
+        row(&[true]),
+    ]));
+    framework.r#move(FrameworkDirection::Up).unwrap();
+
+    framework.select_with(1).unwrap();
+    assert_eq!(CursorState::Hover(0, 0), framework.cursor);
+
+    framework.select_with("rename").unwrap();
+    assert_eq!(CursorState::Selected(0, 0), framework.cursor);
+    assert_eq!(
+        Some(&String::from("rename")),
+        framework.data.global.get::<Received>()
+    );
+
+    framework.deselect().unwrap();
+    framework.r#move(FrameworkDirection::Down).unwrap();
+    framework.select_with(1).unwrap();
+    assert_eq!(CursorState::Selected(0, 1), framework.cursor);
+}
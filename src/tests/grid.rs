@@ -1,6 +1,13 @@
-use ratatui::layout::{Constraint, Rect};
+use std::collections::HashMap;
 
-use crate::widgets::{Grid, GridError};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Color, Style},
+    widgets::{BorderType, Borders, Widget},
+};
+
+use crate::widgets::{BorderHit, Distribute, Grid, GridError, Overflow};
 
 #[test]
 fn length() {
@@ -74,3 +81,358 @@ fn chunks() {
         );
     }
 }
+
+#[test]
+fn background() {
+    let widths = vec![Constraint::Percentage(50), Constraint::Percentage(50)];
+    let heights = vec![Constraint::Percentage(100)];
+    let grid = Grid::new(widths, heights)
+        .unwrap()
+        .background(Style::default().bg(Color::Blue));
+    let area = Rect::new(0, 0, 7, 4);
+    let mut buf = Buffer::empty(area);
+    buf.set_string(1, 1, "xx", Style::default().bg(Color::Red));
+
+    grid.render(area, &mut buf);
+
+    assert_eq!(Color::Blue, buf[(1, 1)].bg);
+    assert_eq!(Color::Blue, buf[(4, 2)].bg);
+}
+
+#[test]
+fn cell_rects() {
+    let widths = vec![Constraint::Percentage(50), Constraint::Percentage(50)];
+    let heights = vec![
+        Constraint::Percentage(30),
+        Constraint::Percentage(30),
+        Constraint::Percentage(40),
+    ];
+    let grid = Grid::new(widths, heights).unwrap();
+    let area = Rect::new(5, 5, 40, 30);
+
+    let chunks = grid.chunks(area).unwrap();
+    let cell_rects = grid.cell_rects(area).unwrap().collect::<Vec<_>>();
+
+    assert_eq!(6, cell_rects.len());
+    cell_rects
+        .into_iter()
+        .for_each(|((x, y), rect)| assert_eq!(chunks[y][x], rect));
+}
+
+#[test]
+fn zero_sized() {
+    let widths = vec![Constraint::Length(0), Constraint::Percentage(100)];
+    let heights = vec![Constraint::Percentage(60), Constraint::Percentage(40)];
+    let grid = Grid::new(widths, heights).unwrap();
+    let area = Rect::new(0, 0, 11, 4);
+
+    let chunks = grid.chunks(area).unwrap();
+
+    assert_eq!(Rect::new(1, 1, 0, 0), chunks[0][0]);
+    assert!(chunks[0].iter().all(|chunk| chunk.is_empty()));
+    assert!(chunks.iter().all(|row| row[0].is_empty()));
+    // too short to draw the lines, same as `render()`
+    assert_eq!(
+        Err(GridError::NotEnoughLength),
+        grid.chunks(Rect::new(0, 0, 11, 3))
+    );
+
+    let mut buf = Buffer::empty(area);
+    grid.clone().render(area, &mut buf);
+    grid.render(Rect::new(0, 0, 1, 1), &mut buf);
+}
+
+#[test]
+fn distribute() {
+    let constraints = vec![
+        Constraint::Percentage(30),
+        Constraint::Percentage(30),
+        Constraint::Percentage(30),
+    ];
+    let length = 103;
+
+    assert_eq!(
+        vec![30, 30, 40],
+        Grid::lengths_distributed(&constraints, length, Distribute::Last).unwrap()
+    );
+    assert_eq!(
+        vec![40, 30, 30],
+        Grid::lengths_distributed(&constraints, length, Distribute::First).unwrap()
+    );
+    assert_eq!(
+        vec![34, 33, 33],
+        Grid::lengths_distributed(&constraints, length, Distribute::Even).unwrap()
+    );
+
+    let grid = Grid::new(constraints.clone(), vec![Constraint::Percentage(100)])
+        .unwrap()
+        .distribute(Distribute::Even);
+    assert_eq!(vec![34, 33, 33], grid.widths(length + 1).unwrap());
+}
+
+#[test]
+fn sub_grid() {
+    let widths = vec![Constraint::Percentage(50), Constraint::Percentage(50)];
+    let heights = vec![Constraint::Percentage(100)];
+    let grid = Grid::new(widths, heights).unwrap();
+    let area = Rect::new(0, 0, 21, 11);
+
+    let inner = Grid::new(
+        vec![Constraint::Percentage(50), Constraint::Percentage(50)],
+        vec![Constraint::Percentage(100)],
+    )
+    .unwrap();
+
+    let cell = grid.chunks(area).unwrap()[0][1];
+    let sub_chunks = grid.sub_grid(area, 1, 0, &inner).unwrap();
+
+    assert_eq!(inner.chunks(cell).unwrap(), sub_chunks);
+    assert_eq!(1, sub_chunks.len());
+    assert_eq!(2, sub_chunks[0].len());
+    assert!(sub_chunks
+        .iter()
+        .flatten()
+        .all(|chunk| cell.contains(chunk.as_position())));
+
+    assert_eq!(
+        GridError::NoSuchCell,
+        grid.sub_grid(area, 2, 0, &inner).unwrap_err()
+    );
+}
+
+#[test]
+fn scroll() {
+    let widths = vec![
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Length(10),
+    ];
+    let heights = vec![Constraint::Length(3)];
+    let grid = Grid::new(widths, heights)
+        .unwrap()
+        .overflow(Overflow::Scroll)
+        .scroll((5, 0));
+    let area = Rect::new(0, 0, 15, 5);
+
+    let chunks = grid.chunks(area).unwrap();
+
+    assert_eq!(Rect::new(0, 1, 6, 3), chunks[0][0]);
+    assert_eq!(Rect::new(7, 1, 8, 3), chunks[0][1]);
+    assert_eq!(0, chunks[0][2].width);
+
+    let mut buf = Buffer::empty(area);
+    grid.render(area, &mut buf);
+
+    assert_eq!(" ", buf[(0, 2)].symbol());
+    assert_eq!("─", buf[(0, 0)].symbol());
+    assert_eq!("│", buf[(6, 2)].symbol());
+    assert_eq!("─", buf[(14, 4)].symbol());
+}
+
+#[test]
+fn min_area() {
+    let grid = Grid::new(
+        vec![Constraint::Length(10), Constraint::Percentage(50)],
+        vec![Constraint::Min(3)],
+    )
+    .unwrap();
+
+    let (width, height) = grid.min_area();
+    assert_eq!((13, 5), (width, height));
+    assert!(grid.chunks(Rect::new(0, 0, width, height)).is_ok());
+
+    let grid = Grid::new(
+        vec![Constraint::Percentage(50), Constraint::Percentage(50)],
+        vec![Constraint::Percentage(100)],
+    )
+    .unwrap();
+
+    let (width, height) = grid.min_area();
+    assert_eq!((4, 3), (width, height));
+    assert!(grid.chunks(Rect::new(0, 0, width, height)).is_ok());
+    assert!(grid.chunks(Rect::new(0, 0, width - 1, height)).is_err());
+    assert!(grid.chunks(Rect::new(0, 0, width, height - 1)).is_err());
+}
+
+#[test]
+fn cell_borders() {
+    let grid = Grid::new(vec![Constraint::Length(2); 3], vec![Constraint::Length(1)])
+        .unwrap()
+        .cell_borders(HashMap::from([
+            ((0, 0), Borders::NONE),
+            ((1, 0), Borders::ALL),
+            ((2, 0), Borders::NONE),
+        ]));
+
+    let area = Rect::new(0, 0, 10, 3);
+    let mut buf = Buffer::empty(area);
+    grid.render(area, &mut buf);
+
+    let lines = (0..area.height)
+        .map(|y| {
+            (0..area.width)
+                .map(|x| buf[(x, y)].symbol())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(vec!["   ┌──┐   ", "   │  │   ", "   └──┘   "], lines);
+}
+
+#[test]
+fn chunks_cached() {
+    let mut grid = Grid::new(
+        vec![Constraint::Length(3), Constraint::Min(0)],
+        vec![Constraint::Percentage(50), Constraint::Percentage(50)],
+    )
+    .unwrap();
+    let area = Rect::new(0, 0, 20, 10);
+
+    let chunks = grid.chunks(area).unwrap();
+    assert_eq!(chunks, grid.chunks_cached(area).unwrap());
+    assert_eq!(chunks, grid.chunks_cached(area).unwrap());
+
+    // changing fields directly also invalidates the cache
+    grid.widths = vec![Constraint::Length(5), Constraint::Min(0)];
+    assert_eq!(
+        grid.chunks(area).unwrap(),
+        grid.chunks_cached(area).unwrap()
+    );
+    assert_ne!(chunks, grid.chunks_cached(area).unwrap());
+
+    grid.overflow = Overflow::Scroll;
+    grid.scroll = (2, 0);
+    assert_eq!(
+        grid.chunks(area).unwrap(),
+        grid.chunks_cached(area).unwrap()
+    );
+
+    let resized = Rect::new(0, 0, 30, 10);
+    assert_eq!(
+        grid.chunks(resized).unwrap(),
+        grid.chunks_cached(resized).unwrap()
+    );
+}
+
+#[test]
+fn border_hit() {
+    let grid = Grid::new(
+        vec![Constraint::Length(4), Constraint::Min(0)],
+        vec![Constraint::Length(2), Constraint::Min(0)],
+    )
+    .unwrap();
+    let area = Rect::new(1, 1, 12, 8);
+
+    assert_eq!(Some(BorderHit::Vertical(0)), grid.border_hit(area, 1, 2));
+    assert_eq!(Some(BorderHit::Vertical(1)), grid.border_hit(area, 6, 5));
+    assert_eq!(Some(BorderHit::Horizontal(1)), grid.border_hit(area, 8, 4));
+    assert_eq!(
+        Some(BorderHit::Intersection(1, 1)),
+        grid.border_hit(area, 6, 4)
+    );
+    assert_eq!(None, grid.border_hit(area, 3, 2));
+    assert_eq!(None, grid.border_hit(area, 0, 0));
+}
+
+#[test]
+fn flex() {
+    let constraints = vec![Constraint::Length(10); 3];
+
+    assert_eq!(
+        vec![10, 20, 30],
+        Grid::lengths_flex(&constraints, 63, Distribute::Last, &[0, 1, 2]).unwrap()
+    );
+    assert_eq!(
+        vec![10, 21, 30],
+        Grid::lengths_flex(&constraints, 64, Distribute::Last, &[0, 1, 2]).unwrap()
+    );
+    assert_eq!(
+        vec![10, 10, 40],
+        Grid::lengths_flex(&constraints, 63, Distribute::Last, &[0, 0, 0]).unwrap()
+    );
+
+    let grid = Grid::new(constraints, vec![Constraint::Length(1)])
+        .unwrap()
+        .width_flex(vec![0, 1, 2]);
+    assert_eq!(vec![10, 20, 30], grid.widths(64).unwrap());
+}
+
+#[test]
+fn rounded_outer_only() {
+    let grid = Grid::new(
+        vec![Constraint::Length(2); 2],
+        vec![Constraint::Length(1); 2],
+    )
+    .unwrap()
+    .border_type(BorderType::Rounded)
+    .rounded_outer_only(true);
+
+    let area = Rect::new(0, 0, 7, 5);
+    let mut buf = Buffer::empty(area);
+    grid.render(area, &mut buf);
+
+    let lines = (0..area.height)
+        .map(|y| {
+            (0..area.width)
+                .map(|x| buf[(x, y)].symbol())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec!["╭──┬──╮", "│  │  │", "├──┼──┤", "│  │  │", "╰──┴──╯"],
+        lines
+    );
+}
+
+#[test]
+fn render_and_chunks() {
+    // every chunk is blank inside and has lines right outside of it
+    let check = |grid: Grid, area: Rect| {
+        let grid_chunks = grid.chunks(area).unwrap();
+        let mut expected = Buffer::empty(area);
+        grid.clone().render(area, &mut expected);
+
+        let mut buf = Buffer::empty(area);
+        let chunks = grid.render_and_chunks(area, &mut buf).unwrap();
+        assert_eq!(grid_chunks, chunks);
+        assert_eq!(expected, buf);
+
+        for chunk in chunks.iter().flatten() {
+            for y in chunk.top()..chunk.bottom() {
+                for x in chunk.left()..chunk.right() {
+                    assert_eq!(" ", buf[(x, y)].symbol());
+                }
+                assert_ne!(" ", buf[(chunk.left() - 1, y)].symbol());
+                assert_ne!(" ", buf[(chunk.right(), y)].symbol());
+            }
+            for x in chunk.left()..chunk.right() {
+                assert_ne!(" ", buf[(x, chunk.top() - 1)].symbol());
+                assert_ne!(" ", buf[(x, chunk.bottom())].symbol());
+            }
+        }
+        chunks
+    };
+
+    let chunks = check(
+        Grid::new(
+            vec![Constraint::Length(3), Constraint::Length(2)],
+            vec![Constraint::Length(1); 2],
+        )
+        .unwrap(),
+        Rect::new(2, 1, 10, 5),
+    );
+    assert_eq!(Rect::new(3, 2, 3, 1), chunks[0][0]);
+
+    let chunks = check(
+        Grid::new(
+            vec![Constraint::Percentage(100)],
+            vec![Constraint::Percentage(50); 2],
+        )
+        .unwrap(),
+        Rect::new(0, 0, 5, 10),
+    );
+    assert_eq!(Rect::new(1, 5, 3, 4), chunks[1][0]);
+
+    let grid = Grid::new(vec![Constraint::Length(1)], vec![Constraint::Length(1)]).unwrap();
+    let mut buf = Buffer::empty(Rect::new(0, 0, 2, 2));
+    assert!(grid.render_and_chunks(buf.area, &mut buf).is_err());
+}
@@ -1 +1,10 @@
+#[cfg(feature = "framework")]
+mod framework;
+#[cfg(feature = "widgets")]
 mod grid;
+#[cfg(feature = "run")]
+mod run;
+#[cfg(feature = "widgets")]
+mod textfield;
+#[cfg(feature = "widgets")]
+mod textlist;
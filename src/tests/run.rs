@@ -0,0 +1,50 @@
+use std::{collections::VecDeque, io, ops::ControlFlow};
+
+use crossterm::event::{Event, KeyCode, KeyEvent};
+use ratatui::{backend::TestBackend, Terminal};
+
+use crate::framework::{run_loop, Framework};
+
+#[test]
+fn run_until_break() {
+    let mut framework = Framework::empty();
+    let mut terminal = Terminal::new(TestBackend::new(10, 5)).unwrap();
+    let mut events = VecDeque::from([
+        Event::Key(KeyEvent::from(KeyCode::Char('a'))),
+        Event::Key(KeyEvent::from(KeyCode::Char('q'))),
+        Event::Key(KeyEvent::from(KeyCode::Char('b'))),
+    ]);
+
+    let mut frames = 0;
+    let mut handled = Vec::new();
+
+    run_loop(
+        &mut framework,
+        &mut terminal,
+        || {
+            events
+                .pop_front()
+                .ok_or_else(|| io::Error::from(io::ErrorKind::UnexpectedEof))
+        },
+        |framework, frame| {
+            frames += 1;
+            framework.render(frame);
+        },
+        |_, event| {
+            let Event::Key(key) = event else {
+                return Ok(ControlFlow::Continue(()));
+            };
+
+            handled.push(key.code);
+            if key.code == KeyCode::Char('q') {
+                Ok(ControlFlow::Break(()))
+            } else {
+                Ok(ControlFlow::Continue(()))
+            }
+        },
+    )
+    .unwrap();
+
+    assert_eq!(2, frames);
+    assert_eq!(vec![KeyCode::Char('a'), KeyCode::Char('q')], handled);
+}
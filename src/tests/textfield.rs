@@ -0,0 +1,551 @@
+use std::{cell::Cell, rc::Rc};
+
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    layout::Rect,
+    style::{Color, Style},
+    widgets::Widget,
+};
+
+use crate::widgets::{TextField, TextFieldAction};
+
+fn render(textfield: &TextField, width: u16) -> Buffer {
+    let area = Rect::new(0, 0, width, 1);
+    let mut buf = Buffer::empty(area);
+    textfield.clone().render(area, &mut buf);
+    buf
+}
+
+#[test]
+fn mask() {
+    let mut textfield = TextField::default();
+    textfield.set_width(10);
+    textfield.set_mask(Some('*'));
+    "secret".chars().for_each(|c| textfield.push(c).unwrap());
+
+    {
+        let buf = render(&textfield, 10);
+
+        assert_eq!(
+            "******    ",
+            buf.content[..10]
+                .iter()
+                .map(|cell| cell.symbol())
+                .collect::<String>()
+        );
+    }
+
+    {
+        textfield.set_reveal(true);
+        let buf = render(&textfield, 10);
+
+        assert_eq!(
+            "*****t    ",
+            buf.content[..10]
+                .iter()
+                .map(|cell| cell.symbol())
+                .collect::<String>()
+        );
+    }
+}
+
+#[test]
+fn words() {
+    let mut textfield = TextField::default();
+    textfield.set_width(40);
+    "Héllo, wörld! It's 2 o'clock."
+        .chars()
+        .for_each(|c| textfield.push(c).unwrap());
+
+    assert_eq!(5, textfield.word_count());
+
+    // (cursor, word)
+    [
+        (0, Some("Héllo")),
+        (3, Some("Héllo")),
+        (5, Some("Héllo")),
+        (6, None),
+        (7, Some("wörld")),
+        (14, Some("It's")),
+        (28, Some("o'clock")),
+        (29, None),
+    ]
+    .into_iter()
+    .for_each(|(cursor, word)| {
+        textfield.cursor = cursor;
+        assert_eq!(word.map(String::from), textfield.current_word());
+    });
+}
+
+#[test]
+fn move_to_column() {
+    let mut textfield = TextField::default();
+    textfield.set_width(10);
+    "a界b".chars().for_each(|c| textfield.push(c).unwrap());
+
+    // (col, cursor)
+    [(0, 0), (1, 1), (3, 2), (4, 3), (9, 3)]
+        .into_iter()
+        .for_each(|(col, cursor)| {
+            assert_eq!(cursor, textfield.move_to_column(col).unwrap());
+            assert_eq!(cursor, textfield.cursor);
+        });
+}
+
+#[test]
+fn backspace_empty() {
+    let fired = Rc::new(Cell::new(0));
+    let mut textfield = TextField::default();
+    textfield.set_width(10);
+    textfield.set_on_backspace_empty({
+        let fired = fired.clone();
+        move || fired.set(fired.get() + 1)
+    });
+
+    textfield.pop().unwrap();
+    assert_eq!(1, fired.get());
+
+    "ab".chars().for_each(|c| textfield.push(c).unwrap());
+    textfield.pop().unwrap();
+    assert_eq!(1, fired.get());
+
+    textfield.first().unwrap();
+    textfield.pop().unwrap();
+    assert_eq!(2, fired.get());
+    assert_eq!("a", textfield.content);
+
+    assert!(textfield.clone().on_backspace_empty.is_none());
+}
+
+#[test]
+fn zwj_emoji() {
+    let family = "👨\u{200d}👩\u{200d}👧";
+    let mut textfield = TextField::default();
+    textfield.set_width(10);
+    family.chars().for_each(|c| textfield.push(c).unwrap());
+    assert_eq!(1, textfield.cursor);
+    assert_eq!(family, textfield.content);
+
+    textfield.push('a').unwrap();
+    textfield.insert(0, 'b').unwrap();
+    assert_eq!(format!("b{family}a"), textfield.content);
+    assert_eq!(3, textfield.cursor);
+
+    // a zero width joiner before an emoji would join it with the previous emoji
+    textfield.content = format!("👨{family}");
+    textfield.cursor = 1;
+    assert!(textfield.insert(1, '\u{200d}').is_err());
+    assert_eq!(format!("👨{family}"), textfield.content);
+    assert_eq!(1, textfield.cursor);
+
+    textfield.content = format!("a{family}b");
+    textfield.cursor = 1;
+    textfield.pop().unwrap();
+    assert_eq!(format!("{family}b"), textfield.content);
+    textfield.last().unwrap();
+    textfield.pop().unwrap();
+    assert_eq!(family, textfield.content);
+}
+
+#[test]
+fn selection_scrolled() {
+    let mut textfield = TextField::default();
+    textfield.set_width(5);
+    textfield.set_selection_style(Style::default().bg(Color::Blue));
+    "abcdefghij"
+        .chars()
+        .for_each(|c| textfield.push(c).unwrap());
+    assert_eq!(6, textfield.scroll);
+
+    textfield.set_selection(Some((8, 4)));
+    let buf = render(&textfield, 5);
+
+    assert_eq!(
+        "ghij ",
+        buf.content
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect::<String>()
+    );
+    assert_eq!(
+        vec![true, true, false, false, false],
+        buf.content
+            .iter()
+            .map(|cell| cell.bg == Color::Blue)
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn validator() {
+    let mut textfield = TextField::default();
+    textfield.set_width(10);
+    textfield.set_validator(|content| match content.parse::<u8>() {
+        Ok(_) => Ok(()),
+        Err(e) => Err(e.to_string()),
+    });
+
+    "25".chars().for_each(|c| {
+        assert_eq!(
+            TextFieldAction::Handled,
+            textfield
+                .handle_key(KeyEvent::from(KeyCode::Char(c)))
+                .unwrap()
+        )
+    });
+    assert_eq!(
+        TextFieldAction::Submit(String::from("25")),
+        textfield
+            .handle_key(KeyEvent::from(KeyCode::Enter))
+            .unwrap()
+    );
+
+    textfield
+        .handle_key(KeyEvent::from(KeyCode::Char('6')))
+        .unwrap();
+    assert_eq!(
+        TextFieldAction::SubmitRejected(String::from("number too large to fit in target type")),
+        textfield
+            .clone()
+            .handle_key(KeyEvent::from(KeyCode::Enter))
+            .unwrap()
+    );
+    assert_eq!("256", textfield.content);
+}
+
+#[test]
+fn suffix() {
+    let mut textfield = TextField::default();
+    textfield.set_width(6);
+    textfield.set_suffix(Some(String::from("ms")));
+    "120".chars().for_each(|c| textfield.push(c).unwrap());
+
+    let symbols = |textfield: &TextField| {
+        render(textfield, 6)
+            .content
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect::<String>()
+    };
+
+    assert_eq!("120 ms", symbols(&textfield));
+
+    textfield.right().unwrap();
+    assert_eq!(3, textfield.cursor);
+
+    "45".chars().for_each(|c| textfield.push(c).unwrap());
+    assert_eq!(2, textfield.scroll);
+    assert_eq!("045 ms", symbols(&textfield));
+}
+
+#[test]
+fn cursor_hidden() {
+    let mut textfield = TextField::default();
+    textfield.set_width(5);
+    "abc".chars().for_each(|c| textfield.push(c).unwrap());
+
+    let has_cursor = |textfield: &TextField| {
+        render(textfield, 5)
+            .content
+            .iter()
+            .any(|cell| cell.bg == Color::Gray)
+    };
+
+    assert!(has_cursor(&textfield));
+
+    textfield.set_cursor_visible(false);
+    assert!(!has_cursor(&textfield));
+
+    textfield.left().unwrap();
+    assert!(!has_cursor(&textfield));
+}
+
+#[test]
+fn render_at() {
+    let mut textfield = TextField::default();
+    textfield.set_width(20);
+    "abcdefgh".chars().for_each(|c| textfield.push(c).unwrap());
+
+    let area = Rect::new(2, 0, 4, 1);
+    let mut buf = Buffer::empty(Rect::new(0, 0, 8, 1));
+    textfield.render_at(area, &mut buf);
+
+    let symbols = buf
+        .content
+        .iter()
+        .map(|cell| cell.symbol())
+        .collect::<String>();
+    assert_eq!("  fgh   ", symbols);
+    assert_eq!(Color::Gray, buf[(5, 0)].bg);
+    assert_eq!(0, textfield.scroll);
+}
+
+#[test]
+fn line_home_end() {
+    let mut textfield = TextField::default();
+    textfield.set_width(20);
+    "ab\ncdé\nf"
+        .chars()
+        .for_each(|c| textfield.push(c).unwrap());
+    textfield.cursor = 4;
+
+    textfield.handle_key(KeyEvent::from(KeyCode::Home)).unwrap();
+    assert_eq!(3, textfield.cursor);
+    textfield.handle_key(KeyEvent::from(KeyCode::End)).unwrap();
+    assert_eq!(6, textfield.cursor);
+    textfield.handle_key(KeyEvent::from(KeyCode::End)).unwrap();
+    assert_eq!(6, textfield.cursor);
+
+    textfield
+        .handle_key(KeyEvent::new(KeyCode::Home, KeyModifiers::CONTROL))
+        .unwrap();
+    assert_eq!(0, textfield.cursor);
+    textfield
+        .handle_key(KeyEvent::new(KeyCode::End, KeyModifiers::CONTROL))
+        .unwrap();
+    assert_eq!(8, textfield.cursor);
+    textfield.first().unwrap();
+    assert_eq!(7, textfield.cursor);
+}
+
+#[test]
+fn insert_str() {
+    let mut textfield = TextField::default();
+    textfield.set_width(20);
+    "xy".chars().for_each(|c| textfield.push(c).unwrap());
+    textfield.left().unwrap();
+
+    textfield.insert_str(textfield.cursor, "a\nb").unwrap();
+    assert_eq!("xa by", textfield.content);
+    assert_eq!(4, textfield.cursor);
+
+    textfield.set_paste_newline_replace(None);
+    textfield.insert_str(textfield.cursor, "c\r\nd").unwrap();
+    assert_eq!("xa bcdy", textfield.content);
+    assert_eq!(6, textfield.cursor);
+
+    textfield.set_multiline(true);
+    textfield.insert_str(textfield.cursor, "\n").unwrap();
+    assert_eq!("xa bcd\ny", textfield.content);
+    assert_eq!(7, textfield.cursor);
+}
+
+#[test]
+fn delete_forward() {
+    let mut textfield = TextField::default();
+    textfield.set_width(20);
+    "ae\u{301}bc"
+        .chars()
+        .for_each(|c| textfield.push(c).unwrap());
+    textfield.cursor = 1;
+
+    textfield.delete_forward().unwrap();
+    assert_eq!("abc", textfield.content);
+    assert_eq!(1, textfield.cursor);
+
+    textfield
+        .handle_key(KeyEvent::from(KeyCode::Delete))
+        .unwrap();
+    assert_eq!("ac", textfield.content);
+    assert_eq!(1, textfield.cursor);
+
+    textfield.last().unwrap();
+    textfield.delete_forward().unwrap();
+    assert_eq!("ac", textfield.content);
+    assert_eq!(2, textfield.cursor);
+}
+
+#[test]
+fn highlight_range() {
+    let mut textfield = TextField::default();
+    textfield.set_width(6);
+    "=SUM(A1;B2)"
+        .chars()
+        .for_each(|c| textfield.push(c).unwrap());
+    textfield.set_highlight_range(Some((4, 8)));
+    textfield.scroll = 2;
+    textfield.cursor = 9;
+
+    let highlighted = render(&textfield, 6)
+        .content
+        .iter()
+        .map(|cell| cell.fg == Color::Red)
+        .collect::<Vec<_>>();
+    assert_eq!(vec![false, false, true, true, true, true], highlighted);
+
+    textfield.set_selection(Some((5, 7)));
+    let highlighted = render(&textfield, 6)
+        .content
+        .iter()
+        .map(|cell| cell.fg == Color::Red)
+        .collect::<Vec<_>>();
+    assert_eq!(vec![false, false, true, false, false, true], highlighted);
+}
+
+#[test]
+fn cursor_screen_pos() {
+    let mut textfield = TextField::default();
+    textfield.set_width(6);
+    textfield.set_report_cursor(true);
+    "hello world"
+        .chars()
+        .for_each(|c| textfield.push(c).unwrap());
+
+    let area = Rect::new(3, 2, 6, 1);
+    assert_eq!(6, textfield.scroll);
+    assert_eq!(Some((8, 2)), textfield.cursor_screen_pos(area));
+    assert!(render(&textfield, 6)
+        .content
+        .iter()
+        .all(|cell| cell.bg != Color::Gray));
+
+    textfield.doc_start().unwrap();
+    "日本".chars().for_each(|c| textfield.push(c).unwrap());
+    assert_eq!(Some((7, 2)), textfield.cursor_screen_pos(area));
+
+    assert_eq!(None, textfield.cursor_screen_pos(Rect::new(3, 2, 4, 1)));
+}
+
+#[test]
+fn trim_on_submit() {
+    let mut textfield = TextField::default();
+    textfield.set_width(10);
+    textfield.insert_str(0, "  hi  ").unwrap();
+    assert_eq!("hi", textfield.trimmed_content());
+
+    let enter = KeyEvent::from(KeyCode::Enter);
+    assert_eq!(
+        TextFieldAction::Submit(String::from("  hi  ")),
+        textfield.handle_key(enter).unwrap()
+    );
+
+    textfield.set_trim_on_submit(true);
+    assert_eq!(
+        TextFieldAction::Submit(String::from("hi")),
+        textfield.handle_key(enter).unwrap()
+    );
+    assert_eq!("  hi  ", textfield.content);
+}
+
+#[test]
+fn inactive_cursor_style() {
+    let mut textfield = TextField::default();
+    textfield.set_width(4);
+    textfield.insert_str(0, "ab").unwrap();
+    textfield.left().unwrap();
+
+    let bg = |textfield: &TextField| {
+        render(textfield, 4)
+            .content
+            .iter()
+            .map(|cell| cell.bg)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(Color::Gray, bg(&textfield)[1]);
+
+    textfield.set_active(false);
+    assert!(bg(&textfield).iter().all(|bg| *bg != Color::Gray));
+
+    textfield.set_inactive_cursor_style(Style::default().bg(Color::DarkGray));
+    assert_eq!(Color::DarkGray, bg(&textfield)[1]);
+    assert_ne!(Color::DarkGray, bg(&textfield)[0]);
+}
+
+#[test]
+fn wrapped_height() {
+    let mut textfield = TextField::default();
+    textfield.set_width(4);
+    assert_eq!(1, textfield.wrapped_height(4));
+
+    textfield.insert_str(0, "abcd").unwrap();
+    assert_eq!(1, textfield.wrapped_height(4));
+    assert_eq!(2, textfield.wrapped_height(3));
+
+    textfield.push('e').unwrap();
+    assert_eq!(2, textfield.wrapped_height(4));
+
+    textfield.content = String::from("ab日本");
+    assert_eq!(2, textfield.wrapped_height(5));
+    assert_eq!(0, textfield.wrapped_height(0));
+}
+
+#[test]
+fn read_only() {
+    let mut textfield = TextField::default();
+    textfield.set_width(4);
+    textfield.insert_str(0, "/usr/bin").unwrap();
+    textfield.set_read_only(true);
+
+    textfield.push('x').unwrap();
+    textfield.insert_str(0, "abc").unwrap();
+    textfield.pop().unwrap();
+    textfield.delete_forward().unwrap();
+    assert_eq!(
+        TextFieldAction::Handled,
+        textfield
+            .handle_key(KeyEvent::from(KeyCode::Backspace))
+            .unwrap()
+    );
+    assert_eq!("/usr/bin", textfield.content);
+    assert!(render(&textfield, 4)
+        .content
+        .iter()
+        .all(|cell| cell.bg != Color::Gray));
+
+    textfield.first().unwrap();
+    assert_eq!((0, 0), (textfield.cursor, textfield.scroll));
+}
+
+#[test]
+fn scroll_indicator() {
+    let mut textfield = TextField::default();
+    textfield.set_width(5);
+    textfield.set_scroll_indicator(true);
+    textfield.insert_str(0, "abcd").unwrap();
+    assert_eq!(0, textfield.scroll);
+
+    textfield.insert_str(4, "efgh").unwrap();
+    assert_eq!(5, textfield.scroll);
+    let symbols = render(&textfield, 5)
+        .content
+        .iter()
+        .map(|cell| cell.symbol().to_string())
+        .collect::<String>();
+    assert_eq!("<fgh ", symbols);
+    assert_eq!(
+        Some((4, 0)),
+        textfield.cursor_screen_pos(Rect::new(0, 0, 5, 1))
+    );
+
+    textfield.doc_start().unwrap();
+    assert_eq!(0, textfield.scroll);
+    assert_eq!(
+        "abcde",
+        render(&textfield, 5)
+            .content
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect::<String>()
+    );
+}
+
+#[test]
+fn modified() {
+    let mut textfield = TextField::default();
+    textfield.set_width(10);
+    textfield.set_content(String::from("abc")).unwrap();
+    assert_eq!(3, textfield.cursor);
+    assert!(!textfield.is_modified());
+
+    textfield.push('d').unwrap();
+    assert!(textfield.is_modified());
+    textfield.pop().unwrap();
+    assert!(!textfield.is_modified());
+
+    textfield.pop().unwrap();
+    assert!(textfield.is_modified());
+    textfield.mark_saved();
+    assert!(!textfield.is_modified());
+    assert_eq!("ab", textfield.saved_content);
+}
@@ -0,0 +1,609 @@
+use std::{cell::Cell, collections::HashSet, rc::Rc};
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    widgets::Widget,
+};
+
+use crate::widgets::{TextList, TextListError, TrimType, WrapMode};
+
+fn render(textlist: &TextList, width: u16) -> Buffer {
+    let area = Rect::new(0, 0, width, textlist.height.unwrap());
+    let mut buf = Buffer::empty(area);
+    textlist.clone().render(area, &mut buf);
+    buf
+}
+
+fn lines(buf: &Buffer) -> Vec<String> {
+    (0..buf.area.height)
+        .map(|y| {
+            (0..buf.area.width)
+                .map(|x| buf[(x, y)].symbol())
+                .collect::<String>()
+                .trim_end()
+                .to_string()
+        })
+        .collect()
+}
+
+fn textlist(len: usize, height: u16) -> TextList {
+    TextList::default()
+        .height(height)
+        .items(&(0..len).collect::<Vec<_>>())
+        .unwrap()
+}
+
+#[test]
+fn vim_key() {
+    let mut textlist = textlist(20, 10);
+
+    assert!(textlist.vim_key('G').unwrap());
+    assert_eq!(19, textlist.selected);
+
+    assert!(textlist.vim_key('g').unwrap());
+    assert_eq!(19, textlist.selected);
+    assert!(textlist.vim_key('g').unwrap());
+    assert_eq!(0, textlist.selected);
+
+    assert!(textlist.vim_key('3').unwrap());
+    assert!(textlist.vim_key('j').unwrap());
+    assert_eq!(3, textlist.selected);
+
+    assert!(textlist.vim_key('1').unwrap());
+    assert!(textlist.vim_key('2').unwrap());
+    assert!(textlist.vim_key('G').unwrap());
+    assert_eq!(11, textlist.selected);
+
+    assert!(!textlist.vim_key('x').unwrap());
+    assert!(textlist.vim_key('j').unwrap());
+    assert_eq!(12, textlist.selected);
+}
+
+#[test]
+fn focused() {
+    let mut textlist = textlist(3, 5).selected_style(Style::default().fg(Color::Red));
+    textlist.set_selected(1).unwrap();
+
+    {
+        let buf = render(&textlist, 6);
+
+        assert_eq!(vec![" 0", "┌────┐", "│1   │", "└────┘", " 2"], lines(&buf));
+    }
+
+    {
+        textlist.set_focused(false);
+        let buf = render(&textlist, 6);
+
+        assert_eq!(vec![" 0", " 1", " 2", "", ""], lines(&buf));
+        assert_eq!(Color::Red, buf[(1, 1)].fg);
+    }
+}
+
+#[test]
+fn unfocused_scroll() {
+    let mut textlist = textlist(10, 5).focused(false);
+    for _ in 0..4 {
+        textlist.down().unwrap();
+    }
+
+    assert_eq!((4, 0), (textlist.selected, textlist.scroll));
+    assert_eq!(
+        vec![" 0", " 1", " 2", " 3", " 4"],
+        lines(&render(&textlist, 6))
+    );
+
+    textlist.down().unwrap();
+    assert_eq!((5, 1), (textlist.selected, textlist.scroll));
+}
+
+#[test]
+fn loading() {
+    let mut textlist = textlist(10, 5).loading(true);
+
+    assert_eq!(
+        vec!["", "", " Loading...", "", ""],
+        lines(&render(&textlist, 12))
+    );
+
+    textlist.down().unwrap();
+    textlist.last().unwrap();
+    textlist.pagedown().unwrap();
+    assert_eq!(0, textlist.selected);
+
+    textlist.set_loading(false);
+    textlist.down().unwrap();
+    assert_eq!(1, textlist.selected);
+}
+
+#[test]
+fn set_items_clamp() {
+    let mut textlist = textlist(20, 5);
+    textlist.last().unwrap();
+    assert_eq!(19, textlist.selected);
+
+    textlist.set_items(&[1, 2, 3]).unwrap();
+    assert_eq!(2, textlist.selected);
+    assert_eq!(
+        vec!["┌────┐", "│3   │", "└────┘", "", ""],
+        lines(&render(&textlist, 6))
+    );
+
+    textlist.set_items::<u8>(&[]).unwrap();
+    assert_eq!(0, textlist.selected);
+    assert_eq!(0, textlist.scroll);
+}
+
+#[test]
+fn set_items_iter() {
+    let mut textlist = TextList::default().height(5);
+    textlist
+        .set_items_iter((1..1000).map(|i| format!("item {i}")))
+        .unwrap();
+
+    assert_eq!(999, textlist.items.len());
+    assert_eq!("item 1", textlist.items[0]);
+    assert_eq!("item 999", textlist.items[998]);
+}
+
+#[test]
+fn ensure_visible() {
+    let mut textlist = textlist(20, 5);
+
+    textlist.ensure_visible(10).unwrap();
+    assert_eq!(8, textlist.scroll);
+    assert_eq!(0, textlist.selected);
+
+    textlist.ensure_visible(9).unwrap();
+    assert_eq!(8, textlist.scroll);
+
+    textlist.ensure_visible(100).unwrap();
+    assert_eq!(17, textlist.scroll);
+
+    textlist.ensure_visible(3).unwrap();
+    assert_eq!(3, textlist.scroll);
+    assert_eq!(0, textlist.selected);
+}
+
+#[test]
+fn overflow_markers() {
+    let mut textlist = textlist(20, 5).overflow_markers(true);
+    textlist.set_selected(10).unwrap();
+
+    assert_eq!(
+        vec![" 8   ▲", " 9", "┌────┐", "│10  │", "└────▼"],
+        lines(&render(&textlist, 6))
+    );
+
+    textlist.set_selected(17).unwrap();
+    textlist.down().unwrap();
+    textlist.down().unwrap();
+    assert_eq!(
+        vec![" 17  ▲", " 18", "┌────┐", "│19  │", "└────┘"],
+        lines(&render(&textlist, 6))
+    );
+
+    textlist.set_items(&[1, 2]).unwrap();
+    textlist.first().unwrap();
+    assert_eq!(
+        vec!["┌────┐", "│1   │", "└────┘", " 2", ""],
+        lines(&render(&textlist, 6))
+    );
+}
+
+#[test]
+fn center_selection() {
+    let mut textlist = textlist(20, 9).center_selection(true);
+
+    for _ in 0..3 {
+        textlist.down().unwrap();
+        assert_eq!(0, textlist.scroll);
+    }
+
+    for _ in 3..16 {
+        textlist.down().unwrap();
+        assert_eq!(3, textlist.selected - textlist.scroll);
+    }
+
+    textlist.down().unwrap();
+    assert_eq!(13, textlist.scroll);
+
+    textlist.last().unwrap();
+    assert_eq!(13, textlist.scroll);
+
+    textlist.up().unwrap();
+    assert_eq!(13, textlist.scroll);
+}
+
+#[test]
+fn page_overlap() {
+    let jump = |page_overlap| {
+        let mut textlist = textlist(50, 12).page_overlap(page_overlap);
+        textlist.pagedown().unwrap();
+        textlist.selected
+    };
+
+    assert_eq!(10, jump(2));
+    assert_eq!(12, jump(0));
+    assert_eq!(7, jump(5));
+
+    let mut textlist = textlist(50, 12).page_overlap(0);
+    textlist.last().unwrap();
+    textlist.pageup().unwrap();
+    assert_eq!(37, textlist.selected);
+}
+
+#[test]
+fn page_overlap_short_list() {
+    let mut textlist = textlist(6, 10).page_overlap(5);
+    textlist.pagedown().unwrap();
+    assert_eq!((5, 0), (textlist.selected, textlist.scroll));
+}
+
+#[test]
+fn separators_after() {
+    let mut textlist = textlist(10, 7).separators_after(HashSet::from([1]));
+
+    assert_eq!(
+        vec!["┌────┐", "│0   │", "└────┘", " 1", " ────", " 2", " 3"],
+        lines(&render(&textlist, 6))
+    );
+
+    textlist.down().unwrap();
+    textlist.down().unwrap();
+    assert_eq!(2, textlist.selected);
+    assert_eq!(
+        vec![" 0", " 1", " ────", "┌────┐", "│2   │", "└────┘", " 3"],
+        lines(&render(&textlist, 6))
+    );
+
+    textlist.down().unwrap();
+    assert_eq!(0, textlist.scroll);
+    textlist.down().unwrap();
+    assert_eq!(1, textlist.scroll);
+
+    textlist.up().unwrap();
+    textlist.up().unwrap();
+    textlist.up().unwrap();
+    assert_eq!(1, textlist.selected);
+}
+
+#[test]
+fn set_selected_bounds() {
+    let mut textlist = textlist(5, 5);
+
+    textlist.set_selected(3).unwrap();
+    assert_eq!(3, textlist.selected);
+    textlist.set_selected(10).unwrap();
+    assert_eq!(4, textlist.selected);
+
+    textlist.try_set_selected(1).unwrap();
+    assert_eq!(1, textlist.selected);
+    assert!(matches!(
+        textlist.try_set_selected(5),
+        Err(TextListError::IndexOutOfBounds)
+    ));
+    assert_eq!(1, textlist.selected);
+
+    let mut empty = TextList::default().height(5);
+    empty.try_set_selected(0).unwrap();
+    assert_eq!(0, empty.selected);
+}
+
+#[test]
+fn compact_fallback() {
+    let mut textlist = TextList::default()
+        .items(&["first", "second"])
+        .unwrap()
+        .height(2);
+    textlist.selected = 1;
+
+    assert_eq!(vec!["", ""], lines(&render(&textlist, 10)));
+
+    textlist.set_compact_fallback(true);
+    assert_eq!(vec!["second", ""], lines(&render(&textlist, 10)));
+    assert_eq!(vec!["s...", ""], lines(&render(&textlist, 4)));
+
+    textlist.items = vec![String::from("first"), String::from("séc")];
+    textlist.set_ascii_only(true);
+    assert_eq!(vec!["s?c", ""], lines(&render(&textlist, 10)));
+}
+
+#[test]
+fn leading_ellipsis() {
+    let textlist = TextList::default()
+        .items(&["short", "error: connection refused", "日本語のログ"])
+        .unwrap()
+        .height(5)
+        .trim_type(TrimType::LeadingEllipsis);
+
+    let lines = lines(&render(&textlist, 12));
+    assert_eq!(" …n refused", lines[3]);
+    // wide graphemes are followed by blank cells in the buffer
+    assert_eq!("…語のログ", lines[4].replace(' ', ""));
+}
+
+#[test]
+fn render_ref() {
+    let mut textlist = textlist(100_000, 8)
+        .separators_after(HashSet::from([5]))
+        .overflow_markers(true);
+    textlist.set_selected(50_000).unwrap();
+
+    let area = Rect::new(0, 0, 12, 8);
+    let mut buf = Buffer::empty(area);
+    textlist.render_ref(area, &mut buf);
+
+    assert_eq!(render(&textlist, 12), buf);
+    assert_eq!(100_000, textlist.items.len());
+}
+
+#[test]
+fn sanitize() {
+    let items = ["first\nline", "a\tb\r\nc\u{7}"];
+    let textlist = TextList::default()
+        .sanitize(true)
+        .tab_width(2)
+        .items(&items)
+        .unwrap()
+        .height(4);
+
+    assert_eq!(vec!["first line", "a  b c"], textlist.items);
+    assert_eq!(
+        vec!["┌──────────┐", "│first line│", "└──────────┘", " a  b c"],
+        lines(&render(&textlist, 12))
+    );
+
+    let textlist = TextList::default().items(&items).unwrap();
+    assert_eq!(items.to_vec(), textlist.items);
+}
+
+#[test]
+fn collapsed() {
+    let mut textlist = textlist(5, 3).collapsed(true);
+    textlist.set_selected(2).unwrap();
+    assert_eq!(vec!["2        ▾", "", ""], lines(&render(&textlist, 10)));
+
+    let textlist = TextList::default()
+        .items(&["a long item"])
+        .unwrap()
+        .collapsed(true);
+    let area = Rect::new(0, 0, 8, 1);
+    let mut buf = Buffer::empty(area);
+    textlist.render_ref(area, &mut buf);
+    assert_eq!(vec!["a l... ▾"], lines(&buf));
+
+    let textlist = TextList::default()
+        .items(&["日本"])
+        .unwrap()
+        .ascii_only(true)
+        .collapsed(true);
+    let mut buf = Buffer::empty(area);
+    textlist.render_ref(area, &mut buf);
+    assert_eq!(vec!["??     ▾"], lines(&buf));
+}
+
+#[test]
+fn rows_for() {
+    let mut textlist = textlist(5, 10);
+    assert_eq!(3, textlist.selected_box_height());
+    assert_eq!(0, textlist.rows_for(0));
+    assert_eq!(3, textlist.rows_for(1));
+    assert_eq!(7, textlist.rows_for(5));
+
+    textlist.focused = false;
+    assert_eq!(1, textlist.selected_box_height());
+    assert_eq!(5, textlist.rows_for(5));
+
+    let textlist = TextList::default();
+    assert_eq!(1, textlist.selected_box_height());
+}
+
+#[test]
+fn on_near_end() {
+    let pages = Rc::new(Cell::new(0));
+    let mut textlist = textlist(3, 10).near_end_threshold(1).on_near_end({
+        let pages = pages.clone();
+        move || {
+            pages.set(pages.get() + 1);
+            vec![String::from("x"), String::from("y")]
+        }
+    });
+
+    textlist.down().unwrap();
+    assert_eq!(0, pages.get());
+    textlist.down().unwrap();
+    assert_eq!(1, pages.get());
+    assert_eq!(5, textlist.items.len());
+
+    textlist.pagedown().unwrap();
+    assert_eq!(4, textlist.selected);
+    assert_eq!(2, pages.get());
+    assert_eq!(7, textlist.items.len());
+    assert!(textlist.clone().on_near_end.is_none());
+}
+
+#[test]
+fn ascii_only_graphemes() {
+    let mut textlist = TextList::default()
+        .items(&["a👨‍👩‍👧b", "e\u{301}"])
+        .unwrap()
+        .height(4);
+    textlist.ascii_only = true;
+
+    assert_eq!(
+        vec!["┌──────┐", "│a?b   │", "└──────┘", " ?"],
+        lines(&render(&textlist, 8))
+    );
+}
+
+#[test]
+fn scrollbar() {
+    let mut textlist = textlist(12, 6).scrollbar(true);
+    let scrollbar = |textlist: &TextList| {
+        let buf = render(textlist, 8);
+        (0..6)
+            .map(|y| buf[(7, y)].symbol().to_string())
+            .collect::<String>()
+    };
+
+    assert_eq!("██││││", scrollbar(&textlist));
+    textlist.set_selected(6).unwrap();
+    assert_eq!("│██│││", scrollbar(&textlist));
+    textlist.last().unwrap();
+    assert_eq!("││││██", scrollbar(&textlist));
+    assert_eq!("│11   │█", lines(&render(&textlist, 8))[4]);
+
+    let textlist = TextList::default()
+        .items(&["a long item", "b"])
+        .unwrap()
+        .height(6)
+        .scrollbar(true);
+    assert_eq!("      ", scrollbar(&textlist));
+    assert_eq!(
+        vec!["┌─────┐", "│a ...│", "└─────┘", " b", "", ""],
+        lines(&render(&textlist, 8))
+    );
+}
+
+#[test]
+fn scroll_up_down() {
+    let mut textlist = textlist(20, 6);
+
+    textlist.scroll_down(3).unwrap();
+    assert_eq!((0, 3), (textlist.selected, textlist.scroll));
+    assert_eq!(" 3", lines(&render(&textlist, 6))[0]);
+
+    textlist.scroll_down(100).unwrap();
+    assert_eq!(16, textlist.scroll);
+    textlist.scroll_up(5).unwrap();
+    assert_eq!((0, 11), (textlist.selected, textlist.scroll));
+
+    textlist.down().unwrap();
+    assert_eq!((1, 1), (textlist.selected, textlist.scroll));
+    textlist.scroll_up(5).unwrap();
+    assert_eq!(0, textlist.scroll);
+}
+
+#[test]
+fn show_position_title() {
+    let mut textlist = textlist(5, 5).show_position_title(true);
+    assert_eq!("┌1/5─────┐", lines(&render(&textlist, 10))[0]);
+
+    textlist.down().unwrap();
+    assert_eq!("┌2/5─────┐", lines(&render(&textlist, 10))[1]);
+
+    textlist.set_cursor_title(Some(String::from("Pick")));
+    assert_eq!("┌Pick────┐", lines(&render(&textlist, 10))[1]);
+}
+
+#[test]
+fn wrap_mode() {
+    let mut textlist = TextList::default()
+        .items(&["short", "a long item that wraps", "x"])
+        .unwrap()
+        .height(8)
+        .width(10)
+        .wrap_mode(WrapMode::Wrap);
+
+    assert_eq!(
+        vec![
+            "┌────────┐",
+            "│short   │",
+            "└────────┘",
+            " a long i",
+            " tem that",
+            "  wraps",
+            " x",
+            ""
+        ],
+        lines(&render(&textlist, 10))
+    );
+
+    textlist.down().unwrap();
+    assert_eq!(
+        vec![
+            " short",
+            "┌────────┐",
+            "│a long i│",
+            "│tem that│",
+            "│ wraps  │",
+            "└────────┘",
+            " x",
+            ""
+        ],
+        lines(&render(&textlist, 10))
+    );
+
+    let mut textlist = TextList::default()
+        .items(&["aaaaaaaaaaaaaaaaaaaaaaaa"; 5])
+        .unwrap()
+        .height(8)
+        .width(10)
+        .wrap_mode(WrapMode::Wrap);
+    textlist.set_selected(2).unwrap();
+    assert_eq!(1, textlist.scroll);
+
+    textlist.first().unwrap();
+    textlist.pagedown().unwrap();
+    assert_eq!((2, 1), (textlist.selected, textlist.scroll));
+}
+
+#[test]
+fn empty_navigation() {
+    let mut textlist = TextList::default().height(5);
+
+    textlist.up().unwrap();
+    textlist.down().unwrap();
+    textlist.pageup().unwrap();
+    textlist.pagedown().unwrap();
+    textlist.first().unwrap();
+    textlist.last().unwrap();
+
+    assert_eq!((0, 0), (textlist.selected, textlist.scroll));
+}
+
+#[test]
+fn jump_to_char() {
+    let mut textlist = TextList::default()
+        .height(10)
+        .items(&["apple", "Banana", "avocado", "cherry", "blueberry"])
+        .unwrap();
+
+    assert!(textlist.jump_to_char('b').unwrap());
+    assert_eq!(1, textlist.selected);
+    assert!(textlist.jump_to_char('B').unwrap());
+    assert_eq!(4, textlist.selected);
+    assert!(textlist.jump_to_char('b').unwrap());
+    assert_eq!(1, textlist.selected);
+
+    assert!(textlist.jump_to_char('a').unwrap());
+    assert_eq!(2, textlist.selected);
+    assert!(textlist.jump_to_char('a').unwrap());
+    assert_eq!(0, textlist.selected);
+
+    assert!(!textlist.jump_to_char('z').unwrap());
+    assert_eq!(0, textlist.selected);
+}
+
+#[test]
+fn wrap_taller_than_list() {
+    let mut textlist = TextList::default()
+        .items(&["x", "abcdefghijklmnopqrst", "y"])
+        .unwrap()
+        .height(5)
+        .width(7)
+        .wrap_mode(WrapMode::Wrap);
+
+    textlist.down().unwrap();
+    assert_eq!((1, 1), (textlist.selected, textlist.scroll));
+    assert_eq!(
+        vec!["┌─────┐", "│abcde│", "│fghij│", "│klmno│", "└─────┘"],
+        lines(&render(&textlist, 7))
+    );
+
+    textlist.set_selected(0).unwrap();
+    textlist.ensure_visible(1).unwrap();
+    assert_eq!(1, textlist.scroll);
+}
@@ -1,10 +1,13 @@
-use std::{error::Error, fmt::Display};
+use std::{collections::HashMap, error::Error, fmt::Display};
 
 use ratatui::{
-    layout::{Constraint, Rect},
+    layout::{Constraint, Position, Rect},
     style::Style,
-    symbols::{border::Set, line::CROSS},
-    widgets::{BorderType, Widget},
+    symbols::{
+        border::Set,
+        line::{self, CROSS},
+    },
+    widgets::{BorderType, Borders, Widget},
 };
 
 #[derive(Clone)]
@@ -13,6 +16,26 @@ pub struct Grid {
     pub heights: Vec<Constraint>,
     pub border_type: BorderType,
     pub border_style: Style,
+    pub background: Option<Style>,
+    /// Where the leftover length goes when the constraints do not fill up the whole area
+    pub distribute: Distribute,
+    /// Weight of each column when sharing the leftover width, 0 for a fixed column. Leftover goes
+    /// by `distribute` if all weights are 0
+    pub width_flex: Vec<u16>,
+    /// Weight of each row when sharing the leftover height, same as `width_flex`
+    pub height_flex: Vec<u16>,
+    /// What to do when the area is too small to fit `Length` and `Min` constraints
+    pub overflow: Overflow,
+    /// The `(x, y)` offset of the grid, only used when `overflow` is `Overflow::Scroll`
+    pub scroll: (u16, u16),
+    /// Edges each cell wants drawn, an edge is drawn if either cell next to it wants it. Cells
+    /// not in here want all of their edges
+    pub cell_borders: HashMap<(usize, usize), Borders>,
+    /// Inputs and result of the last `chunks_cached()`
+    chunks_cache: Option<(ChunksCacheKey, Vec<Vec<Rect>>)>,
+    /// Use rounded corners for the 4 outer corners only, other junctions use the normal T and
+    /// cross symbols
+    pub rounded_outer_only: bool,
 }
 
 impl Grid {
@@ -25,40 +48,279 @@ impl Grid {
             heights,
             border_type: BorderType::Plain,
             border_style: Style::default(),
+            background: None,
+            distribute: Distribute::default(),
+            width_flex: Vec::new(),
+            height_flex: Vec::new(),
+            overflow: Overflow::default(),
+            scroll: (0, 0),
+            cell_borders: HashMap::new(),
+            chunks_cache: None,
+            rounded_outer_only: false,
         })
     }
 }
 
 impl Grid {
+    /// Return chunks (the area inside each cell) as 2D array of rects
+    ///
+    /// When scrolling, cells partly outside of `area` are clipped and cells completely outside of
+    /// `area` are zero sized
     pub fn chunks(&self, area: Rect) -> Result<Vec<Vec<Rect>>, GridError> {
-        let widths = self.widths(area.width)?;
-        let heights = self.heights(area.height)?;
-
-        let xs = {
-            let mut xs = Self::lines(area.x, &widths);
-            xs.truncate(self.widths.len());
-            xs.iter_mut().for_each(|item| *item += 1);
-            xs
-        };
-        let ys = {
-            let mut ys = Self::lines(area.y, &heights);
-            ys.truncate(self.heights.len());
-            ys.iter_mut().for_each(|item| *item += 1);
-            ys
-        };
+        let (vertical_lines, horizontal_lines) = self.border_lines(area)?;
+        Ok(self.chunks_between(area, &vertical_lines, &horizontal_lines))
+    }
+
+    /// The area which the whole grid is drawn on, and the offset from it to the screen
+    ///
+    /// When scrolling, the grid is drawn on an area starting from `(0, 0)` that is big enough to
+    /// fit all `Length` and `Min` constraints
+    fn viewport(&self, area: Rect) -> (Rect, (i32, i32)) {
+        match self.overflow {
+            Overflow::Shrink => (area, (0, 0)),
+            Overflow::Scroll => (
+                Rect::new(
+                    0,
+                    0,
+                    area.width.max(Self::natural_length(&self.widths)),
+                    area.height.max(Self::natural_length(&self.heights)),
+                ),
+                (
+                    area.x as i32 - self.scroll.0 as i32,
+                    area.y as i32 - self.scroll.1 as i32,
+                ),
+            ),
+        }
+    }
 
-        Ok(ys
+    /// Length needed for all `Length` and `Min` constraints and the lines between them, at least
+    /// the length `lengths_flex()` accepts after the lines are taken out
+    fn natural_length(constraints: &[Constraint]) -> u16 {
+        let lines = constraints.len() as u16 + 1;
+        constraints
             .iter()
-            .zip(heights.iter())
-            .map(|(y, height)| {
-                let row = xs
-                    .iter()
-                    .zip(widths.iter())
-                    .map(|(x, width)| Rect::new(*x, *y, *width, *height - 1))
-                    .collect::<Vec<_>>();
-                row
+            .map(|constraint| match constraint {
+                Constraint::Length(length) | Constraint::Min(length) => *length,
+                _ => 0,
             })
-            .collect::<Vec<_>>())
+            .fold(lines, u16::saturating_add)
+            .max(lines + 1)
+    }
+
+    /// Move `rect` by `offset` and clip it to `area`
+    fn clip(rect: Rect, offset: (i32, i32), area: Rect) -> Rect {
+        let clip_axis = |start: u16, length: u16, offset: i32, min: u16, max: u16| {
+            let start = start as i32 + offset;
+            let end = (start + length as i32).clamp(min as i32, max as i32);
+            let start = start.clamp(min as i32, max as i32);
+            (start as u16, (end - start) as u16)
+        };
+
+        let (x, width) = clip_axis(rect.x, rect.width, offset.0, area.left(), area.right());
+        let (y, height) = clip_axis(rect.y, rect.height, offset.1, area.top(), area.bottom());
+
+        Rect::new(x, y, width, height)
+    }
+
+    /// Positions of the vertical and horizontal lines before applying the offset from
+    /// `viewport()`
+    fn border_lines(&self, area: Rect) -> Result<(Vec<u16>, Vec<u16>), GridError> {
+        let (mut content, _) = self.viewport(area);
+        content.height = content.height.saturating_sub(1);
+
+        let widths = self.widths(content.width)?;
+        let heights = self.heights(content.height)?;
+
+        Ok((
+            Self::lines(content.x, &widths),
+            Self::lines(content.y, &heights),
+        ))
+    }
+
+    /// The cells between the lines from `border_lines()`, moved and clipped to `area` when
+    /// scrolling
+    fn chunks_between(
+        &self,
+        area: Rect,
+        vertical_lines: &[u16],
+        horizontal_lines: &[u16],
+    ) -> Vec<Vec<Rect>> {
+        let (_, offset) = self.viewport(area);
+
+        horizontal_lines
+            .windows(2)
+            .map(|ys| {
+                vertical_lines
+                    .windows(2)
+                    .map(|xs| {
+                        let chunk =
+                            Rect::new(xs[0] + 1, ys[0] + 1, xs[1] - xs[0] - 1, ys[1] - ys[0] - 1);
+                        match self.overflow {
+                            Overflow::Shrink => chunk,
+                            Overflow::Scroll => Self::clip(chunk, offset, area),
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Which line of the grid is at `(col, row)`, `None` if the point is not on a line
+    pub fn border_hit(&self, area: Rect, col: u16, row: u16) -> Option<BorderHit> {
+        if !area.contains(Position::new(col, row)) {
+            return None;
+        }
+
+        let (vertical_lines, horizontal_lines) = self.border_lines(area).ok()?;
+        let (_, (offset_x, offset_y)) = self.viewport(area);
+        let (x, y) = (col as i32 - offset_x, row as i32 - offset_y);
+
+        let within = |lines: &[u16], position: i32| {
+            *lines.first().unwrap() as i32 <= position && position <= *lines.last().unwrap() as i32
+        };
+        if !within(&vertical_lines, x) || !within(&horizontal_lines, y) {
+            return None;
+        }
+
+        let column = vertical_lines.iter().position(|line| *line as i32 == x);
+        let row = horizontal_lines.iter().position(|line| *line as i32 == y);
+
+        match (column, row) {
+            (Some(column), Some(row)) => Some(BorderHit::Intersection(column, row)),
+            (Some(column), None) => Some(BorderHit::Vertical(column)),
+            (None, Some(row)) => Some(BorderHit::Horizontal(row)),
+            (None, None) => None,
+        }
+    }
+
+    /// If the cell at `(x, y)` wants `edge` to be drawn, cells outside of the grid wants nothing
+    fn wants_edge(&self, x: usize, y: usize, edge: Borders) -> bool {
+        if x >= self.widths.len() || y >= self.heights.len() {
+            return false;
+        }
+
+        match self.cell_borders.get(&(x, y)) {
+            Some(borders) => borders.contains(edge),
+            None => true,
+        }
+    }
+
+    /// Symbol where lines meet, by which of the 4 directions has a line
+    fn junction(
+        set: &line::Set,
+        up: bool,
+        down: bool,
+        left: bool,
+        right: bool,
+    ) -> Option<&'static str> {
+        Some(match (up, down, left, right) {
+            (true, true, true, true) => set.cross,
+            (false, true, false, true) => set.top_left,
+            (false, true, true, false) => set.top_right,
+            (true, false, false, true) => set.bottom_left,
+            (true, false, true, false) => set.bottom_right,
+            (true, true, false, true) => set.vertical_right,
+            (true, true, true, false) => set.vertical_left,
+            (false, true, true, true) => set.horizontal_down,
+            (true, false, true, true) => set.horizontal_up,
+            (_, _, false, false) if up || down => set.vertical,
+            (false, false, _, _) if left || right => set.horizontal,
+            _ => return None,
+        })
+    }
+
+    fn line_set(border_type: BorderType) -> line::Set {
+        match border_type {
+            BorderType::Rounded => line::ROUNDED,
+            BorderType::Double => line::DOUBLE,
+            BorderType::Thick => line::THICK,
+            _ => line::NORMAL,
+        }
+    }
+
+    /// Line symbols for a junction, with rounded corners only at the outer corners if
+    /// `self.rounded_outer_only` is `true`
+    fn junction_set(&self, outer_corner: bool) -> line::Set {
+        let set = Self::line_set(self.border_type);
+        if !self.rounded_outer_only {
+            return set;
+        }
+
+        let corners = if outer_corner {
+            line::ROUNDED
+        } else {
+            line::NORMAL
+        };
+        line::Set {
+            top_left: corners.top_left,
+            top_right: corners.top_right,
+            bottom_left: corners.bottom_left,
+            bottom_right: corners.bottom_right,
+            ..set
+        }
+    }
+
+    /// Same as `chunks()`, but returns the previous result if `area` and the fields affecting the
+    /// chunks did not change
+    pub fn chunks_cached(&mut self, area: Rect) -> Result<&[Vec<Rect>], GridError> {
+        let hit = self
+            .chunks_cache
+            .as_ref()
+            .is_some_and(|(key, _)| key.matches(self, area));
+
+        if !hit {
+            let chunks = self.chunks(area)?;
+            self.chunks_cache = Some((ChunksCacheKey::new(self, area), chunks));
+        }
+
+        let (_, chunks) = self.chunks_cache.as_ref().expect("cache is set above");
+        Ok(chunks)
+    }
+
+    pub fn clear_cache(&mut self) {
+        self.chunks_cache = None;
+    }
+
+    /// Smallest `(width, height)` that fits all `Length` and `Min` constraints and the lines
+    /// between them
+    pub fn min_area(&self) -> (u16, u16) {
+        (
+            Self::natural_length(&self.widths),
+            Self::natural_length(&self.heights),
+        )
+    }
+
+    /// Chunks of `inner` when placed in the cell at `(x, y)`
+    pub fn sub_grid(
+        &self,
+        area: Rect,
+        x: usize,
+        y: usize,
+        inner: &Grid,
+    ) -> Result<Vec<Vec<Rect>>, GridError> {
+        let cell = *self
+            .chunks(area)?
+            .get(y)
+            .and_then(|row| row.get(x))
+            .ok_or(GridError::NoSuchCell)?;
+
+        inner.chunks(cell)
+    }
+
+    /// Same as `chunks()`, but as an iterator of `((x, y), Rect)` in reading order
+    pub fn cell_rects(
+        &self,
+        area: Rect,
+    ) -> Result<impl Iterator<Item = ((usize, usize), Rect)>, GridError> {
+        Ok(self
+            .chunks(area)?
+            .into_iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.into_iter()
+                    .enumerate()
+                    .map(move |(x, rect)| ((x, y), rect))
+            }))
     }
 
     pub fn lines(mut position: u16, lengths: &[u16]) -> Vec<u16> {
@@ -74,14 +336,38 @@ impl Grid {
     }
 
     pub fn heights(&self, height: u16) -> Result<Vec<u16>, GridError> {
-        Self::lengths(&self.heights, height)
+        Self::lengths_flex(&self.heights, height, self.distribute, &self.height_flex)
     }
 
     pub fn widths(&self, width: u16) -> Result<Vec<u16>, GridError> {
-        Self::lengths(&self.widths, width - 1)
+        Self::lengths_flex(
+            &self.widths,
+            width.saturating_sub(1),
+            self.distribute,
+            &self.width_flex,
+        )
     }
 
-    pub fn lengths(constraints: &[Constraint], mut length: u16) -> Result<Vec<u16>, GridError> {
+    pub fn lengths(constraints: &[Constraint], length: u16) -> Result<Vec<u16>, GridError> {
+        Self::lengths_distributed(constraints, length, Distribute::Last)
+    }
+
+    pub fn lengths_distributed(
+        constraints: &[Constraint],
+        length: u16,
+        distribute: Distribute,
+    ) -> Result<Vec<u16>, GridError> {
+        Self::lengths_flex(constraints, length, distribute, &[])
+    }
+
+    /// Same as `lengths_distributed()`, but the leftover is shared by the weights in `flex`
+    /// instead, missing weights are 0
+    pub fn lengths_flex(
+        constraints: &[Constraint],
+        mut length: u16,
+        distribute: Distribute,
+        flex: &[u16],
+    ) -> Result<Vec<u16>, GridError> {
         if length < constraints.len() as u16 + 1 {
             return Err(GridError::NotEnoughLength);
         }
@@ -95,7 +381,41 @@ impl Grid {
         let sum: u16 = lengths.iter().sum();
 
         if sum < length {
-            *lengths.last_mut().unwrap() += length - sum;
+            let remainder = length - sum;
+            let weights = flex.iter().take(lengths.len()).map(|weight| *weight as u32);
+            let total = weights.clone().sum::<u32>();
+
+            if total != 0 {
+                let mut given = 0;
+                lengths
+                    .iter_mut()
+                    .zip(weights.clone())
+                    .for_each(|(length, weight)| {
+                        let share = (remainder as u32 * weight / total) as u16;
+                        *length += share;
+                        given += share;
+                    });
+
+                // rounded down shares leave a bit, which goes to the flex cells from the front
+                lengths
+                    .iter_mut()
+                    .zip(weights)
+                    .filter(|(_, weight)| *weight != 0)
+                    .take((remainder - given) as usize)
+                    .for_each(|(length, _)| *length += 1);
+            } else {
+                match distribute {
+                    Distribute::Last => *lengths.last_mut().unwrap() += remainder,
+                    Distribute::First => *lengths.first_mut().unwrap() += remainder,
+                    Distribute::Even => {
+                        let count = lengths.len() as u16;
+                        lengths.iter_mut().enumerate().for_each(|(index, length)| {
+                            *length +=
+                                remainder / count + u16::from((index as u16) < remainder % count)
+                        });
+                    }
+                }
+            }
         }
         // .collect::<Vec<_>>();
 
@@ -160,6 +480,24 @@ impl Grid {
         self
     }
 
+    pub fn set_widths(&mut self, widths: Vec<Constraint>) -> Result<(), GridError> {
+        if widths.is_empty() {
+            return Err(GridError::ZeroLength);
+        }
+
+        self.widths = widths;
+        Ok(())
+    }
+
+    pub fn set_heights(&mut self, heights: Vec<Constraint>) -> Result<(), GridError> {
+        if heights.is_empty() {
+            return Err(GridError::ZeroLength);
+        }
+
+        self.heights = heights;
+        Ok(())
+    }
+
     pub fn set_border_type(&mut self, border_type: BorderType) {
         self.border_type = border_type;
     }
@@ -172,16 +510,108 @@ impl Grid {
     pub fn set_border_style(&mut self, border_style: Style) {
         self.border_style = border_style;
     }
+
+    pub fn background(mut self, background: Style) -> Self {
+        self.set_background(background);
+        self
+    }
+
+    pub fn set_background(&mut self, background: Style) {
+        self.background = Some(background);
+    }
+
+    pub fn overflow(mut self, overflow: Overflow) -> Self {
+        self.set_overflow(overflow);
+        self
+    }
+
+    pub fn set_overflow(&mut self, overflow: Overflow) {
+        self.overflow = overflow;
+    }
+
+    pub fn scroll(mut self, scroll: (u16, u16)) -> Self {
+        self.set_scroll(scroll);
+        self
+    }
+
+    pub fn set_scroll(&mut self, scroll: (u16, u16)) {
+        self.scroll = scroll;
+    }
+
+    pub fn cell_borders(mut self, cell_borders: HashMap<(usize, usize), Borders>) -> Self {
+        self.set_cell_borders(cell_borders);
+        self
+    }
+
+    pub fn set_cell_borders(&mut self, cell_borders: HashMap<(usize, usize), Borders>) {
+        self.cell_borders = cell_borders;
+    }
+
+    pub fn distribute(mut self, distribute: Distribute) -> Self {
+        self.set_distribute(distribute);
+        self
+    }
+
+    pub fn rounded_outer_only(mut self, rounded_outer_only: bool) -> Self {
+        self.set_rounded_outer_only(rounded_outer_only);
+        self
+    }
+
+    pub fn set_rounded_outer_only(&mut self, rounded_outer_only: bool) {
+        self.rounded_outer_only = rounded_outer_only;
+    }
+
+    pub fn set_distribute(&mut self, distribute: Distribute) {
+        self.distribute = distribute;
+    }
+
+    pub fn width_flex(mut self, width_flex: Vec<u16>) -> Self {
+        self.set_width_flex(width_flex);
+        self
+    }
+
+    pub fn set_width_flex(&mut self, width_flex: Vec<u16>) {
+        self.width_flex = width_flex;
+    }
+
+    pub fn height_flex(mut self, height_flex: Vec<u16>) -> Self {
+        self.set_height_flex(height_flex);
+        self
+    }
+
+    pub fn set_height_flex(&mut self, height_flex: Vec<u16>) {
+        self.height_flex = height_flex;
+    }
 }
 
-impl Widget for Grid {
-    fn render(self, mut area: Rect, buf: &mut ratatui::buffer::Buffer) {
-        area.height -= 1;
+impl Grid {
+    /// Draw the grid like `Widget::render()` and return the same chunks as `chunks()` without
+    /// computing the lines twice, nothing is drawn if the area is too small
+    pub fn render_and_chunks(
+        self,
+        area: Rect,
+        buf: &mut ratatui::buffer::Buffer,
+    ) -> Result<Vec<Vec<Rect>>, GridError> {
+        let (vertical_lines, horizontal_lines) = self.border_lines(area)?;
+        let chunks = self.chunks_between(area, &vertical_lines, &horizontal_lines);
+        self.render_lines(area, buf, &vertical_lines, &horizontal_lines);
+        Ok(chunks)
+    }
 
-        let widths = self.widths(area.width).unwrap();
-        let heights = self.heights(area.height).unwrap();
-        let vertical_lines = Self::lines(area.x, &widths);
-        let horizontal_lines = Self::lines(area.y, &heights);
+    /// Draw the background and the lines from `border_lines()`
+    fn render_lines(
+        &self,
+        area: Rect,
+        buf: &mut ratatui::buffer::Buffer,
+        vertical_lines: &[u16],
+        horizontal_lines: &[u16],
+    ) {
+        if let Some(background) = self.background {
+            buf.set_style(area, background);
+        }
+
+        let (_, (offset_x, offset_y)) = self.viewport(area);
+        let (columns, rows) = (vertical_lines.len() - 1, horizontal_lines.len() - 1);
 
         let top = horizontal_lines.first().unwrap();
         let bottom = horizontal_lines.last().unwrap();
@@ -190,11 +620,61 @@ impl Widget for Grid {
 
         let set = BorderType::border_symbols(self.border_type);
 
+        // only draws the parts that are inside of `area`
+        let mut set_string = |x: u16, y: u16, symbol: &str| {
+            let (x, y) = (x as i32 + offset_x, y as i32 + offset_y);
+            if x >= 0 && y >= 0 && area.contains(Position::new(x as u16, y as u16)) {
+                buf.set_string(x as u16, y as u16, symbol, self.border_style);
+            }
+        };
+
+        if !self.cell_borders.is_empty() {
+            let vertical = |x: usize, y: usize| {
+                (x > 0 && self.wants_edge(x - 1, y, Borders::RIGHT))
+                    || self.wants_edge(x, y, Borders::LEFT)
+            };
+            let horizontal = |x: usize, y: usize| {
+                (y > 0 && self.wants_edge(x, y - 1, Borders::BOTTOM))
+                    || self.wants_edge(x, y, Borders::TOP)
+            };
+
+            for (x, line_x) in vertical_lines.iter().enumerate() {
+                for (y, line_y) in horizontal_lines.iter().enumerate() {
+                    let outer_corner = (x == 0 || x == columns) && (y == 0 || y == rows);
+                    let line_set = self.junction_set(outer_corner);
+                    let symbol = Self::junction(
+                        &line_set,
+                        y > 0 && vertical(x, y - 1),
+                        y < rows && vertical(x, y),
+                        x > 0 && horizontal(x - 1, y),
+                        x < columns && horizontal(x, y),
+                    );
+                    if let Some(symbol) = symbol {
+                        set_string(*line_x, *line_y, symbol);
+                    }
+
+                    if y < rows && vertical(x, y) {
+                        for cell_y in *line_y + 1..horizontal_lines[y + 1] {
+                            set_string(*line_x, cell_y, line_set.vertical);
+                        }
+                    }
+
+                    if x < columns && horizontal(x, y) {
+                        for cell_x in *line_x + 1..vertical_lines[x + 1] {
+                            set_string(cell_x, *line_y, line_set.horizontal);
+                        }
+                    }
+                }
+            }
+
+            return;
+        }
+
         // vertical lines
         for x in vertical_lines.iter() {
             for y in *top..*bottom + 1 {
                 if !horizontal_lines.contains(&y) {
-                    buf.set_string(*x, y, set.vertical_left, self.border_style);
+                    set_string(*x, y, set.vertical_left);
                 }
             }
         }
@@ -202,25 +682,119 @@ impl Widget for Grid {
         // horizontal lines
         for y in horizontal_lines.iter() {
             for x in *left..*right + 1 {
-                if vertical_lines.contains(&x) {
-                    buf.set_string(
-                        x,
-                        *y,
-                        Self::from_pos(&x, y, left, right, top, bottom, &set),
-                        self.border_style,
-                    );
+                if vertical_lines.contains(&x) && self.rounded_outer_only {
+                    let (is_top, is_bottom) = (y == top, y == bottom);
+                    let (is_left, is_right) = (x == *left, x == *right);
+                    let line_set =
+                        self.junction_set((is_top || is_bottom) && (is_left || is_right));
+                    let symbol =
+                        Self::junction(&line_set, !is_top, !is_bottom, !is_left, !is_right);
+                    set_string(x, *y, symbol.unwrap_or(line_set.cross));
+                } else if vertical_lines.contains(&x) {
+                    set_string(x, *y, Self::from_pos(&x, y, left, right, top, bottom, &set));
                 } else {
-                    buf.set_string(x, *y, set.horizontal_top, self.border_style);
+                    set_string(x, *y, set.horizontal_top);
                 }
             }
         }
     }
 }
 
+impl Widget for Grid {
+    fn render(self, area: Rect, buf: &mut ratatui::buffer::Buffer) {
+        match self.border_lines(area) {
+            Ok((vertical_lines, horizontal_lines)) => {
+                self.render_lines(area, buf, &vertical_lines, &horizontal_lines)
+            }
+            // nothing can be drawn if there is not enough space for the lines
+            Err(_) => {
+                if let Some(background) = self.background {
+                    buf.set_style(area, background);
+                }
+            }
+        }
+    }
+}
+
+/// Everything `Grid::chunks()` depends on, to check if `Grid.chunks_cache` is still valid
+#[derive(Clone)]
+struct ChunksCacheKey {
+    area: Rect,
+    widths: Vec<Constraint>,
+    heights: Vec<Constraint>,
+    width_flex: Vec<u16>,
+    height_flex: Vec<u16>,
+    distribute: Distribute,
+    overflow: Overflow,
+    scroll: (u16, u16),
+}
+
+impl ChunksCacheKey {
+    fn new(grid: &Grid, area: Rect) -> Self {
+        Self {
+            area,
+            widths: grid.widths.clone(),
+            heights: grid.heights.clone(),
+            width_flex: grid.width_flex.clone(),
+            height_flex: grid.height_flex.clone(),
+            distribute: grid.distribute,
+            overflow: grid.overflow,
+            scroll: grid.scroll,
+        }
+    }
+
+    /// If the key is the same as `ChunksCacheKey::new(grid, area)`, without cloning anything
+    fn matches(&self, grid: &Grid, area: Rect) -> bool {
+        self.area == area
+            && self.widths == grid.widths
+            && self.heights == grid.heights
+            && self.width_flex == grid.width_flex
+            && self.height_flex == grid.height_flex
+            && self.distribute == grid.distribute
+            && self.overflow == grid.overflow
+            && self.scroll == grid.scroll
+    }
+}
+
+/// Where to put the leftover length when the constraints do not fill up the whole area
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Distribute {
+    /// All leftover goes to the last cell
+    #[default]
+    Last,
+    /// Leftover is spread evenly, cells in the front get 1 more if it cannot be evenly divided
+    Even,
+    /// All leftover goes to the first cell
+    First,
+}
+
+/// What to do when the area is too small to fit `Length` and `Min` constraints
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Overflow {
+    /// Shrink the cells to fit into the area
+    #[default]
+    Shrink,
+    /// Keep the cell sizes and scroll by `Grid.scroll`, cells at the edges are clipped
+    Scroll,
+}
+
+/// A line of the grid found by `Grid::border_hit()`, lines are counted from 0 at the top or left
+/// edge of the grid
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BorderHit {
+    /// The vertical line with the index
+    Vertical(usize),
+    /// The horizontal line with the index
+    Horizontal(usize),
+    /// Where the vertical and horizontal lines with the indices meet
+    Intersection(usize, usize),
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum GridError {
     NotEnoughLength,
     ZeroLength,
+    NoSuchCell,
 }
 
 impl Display for GridError {
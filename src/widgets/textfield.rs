@@ -1,13 +1,16 @@
-use std::fmt::Display;
+use std::{fmt::Display, rc::Rc};
 
 use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    layout::Rect,
     style::{Color, Style},
     text::{Line, Span},
     widgets::{Paragraph, Widget},
 };
 use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
-#[derive(Clone)]
 pub struct TextField {
     pub content: String,
     pub scroll: usize,
@@ -16,6 +19,88 @@ pub struct TextField {
     pub text_style: Style,
     pub cursor_style: Style,
     pub width: Option<u16>,
+    /// Character to display in place of every grapheme, `None` to display the content as is
+    pub mask: Option<char>,
+    /// Show the last grapheme unmasked, only useful when `mask` is `Some(_)`
+    pub reveal_last: bool,
+    /// Selected range of graphemes, start inclusive and end exclusive
+    pub selection: Option<(usize, usize)>,
+    /// Style of the selected graphemes
+    pub selection_style: Style,
+    /// Range of graphemes to highlight such as an error, start inclusive and end exclusive
+    pub highlight_range: Option<(usize, usize)>,
+    /// Style of the graphemes in `highlight_range`
+    pub highlight_range_style: Style,
+    /// Text shown after the content which cannot be edited
+    pub suffix: Option<String>,
+    /// Style of `suffix`
+    pub suffix_style: Style,
+    /// Checks the content when submitting with `handle_key()`, the error message is returned in
+    /// `TextFieldAction::SubmitRejected`
+    pub validator: Option<TextFieldValidator>,
+    /// Called when `pop()` is called with the cursor at the start, reset to `None` when cloned
+    pub on_backspace_empty: Option<Box<dyn FnMut()>>,
+    /// Draw the cursor with `cursor_style`, toggle to make the cursor blink
+    pub cursor_visible: bool,
+    /// Keep newlines in text inserted with `insert_str()`
+    pub multiline: bool,
+    /// Character to replace newlines with in `insert_str()` when not `multiline`, `None` to
+    /// remove them
+    pub paste_newline_replace: Option<char>,
+    /// Leave the cursor undrawn so the terminal cursor can be placed at `cursor_screen_pos()`
+    pub report_cursor: bool,
+    /// Trim whitespace at both ends of the submitted text in `handle_key()`, `content` is kept
+    /// as is
+    pub trim_on_submit: bool,
+    /// If the field is focused, the cursor is drawn with `inactive_cursor_style` when `false`
+    pub active: bool,
+    /// Style of the cursor when not `active`, the cursor is hidden if this is `Style::default()`
+    pub inactive_cursor_style: Style,
+    /// Ignore all edits and do not draw the cursor, the cursor can still move to scroll the
+    /// content
+    pub read_only: bool,
+    /// Draw `<` on the first column when the content is scrolled, the content starts from the
+    /// second column then
+    pub scroll_indicator: bool,
+    /// If `content` differs from `saved_content`, updated by every edit
+    pub modified: bool,
+    /// Content as of the last `set_content()` or `mark_saved()`
+    pub saved_content: String,
+}
+
+impl Clone for TextField {
+    fn clone(&self) -> Self {
+        Self {
+            content: self.content.clone(),
+            scroll: self.scroll,
+            cursor: self.cursor,
+            style: self.style,
+            text_style: self.text_style,
+            cursor_style: self.cursor_style,
+            width: self.width,
+            mask: self.mask,
+            reveal_last: self.reveal_last,
+            selection: self.selection,
+            selection_style: self.selection_style,
+            highlight_range: self.highlight_range,
+            highlight_range_style: self.highlight_range_style,
+            suffix: self.suffix.clone(),
+            suffix_style: self.suffix_style,
+            validator: self.validator.clone(),
+            on_backspace_empty: None,
+            cursor_visible: self.cursor_visible,
+            multiline: self.multiline,
+            paste_newline_replace: self.paste_newline_replace,
+            report_cursor: self.report_cursor,
+            trim_on_submit: self.trim_on_submit,
+            active: self.active,
+            inactive_cursor_style: self.inactive_cursor_style,
+            read_only: self.read_only,
+            scroll_indicator: self.scroll_indicator,
+            modified: self.modified,
+            saved_content: self.saved_content.clone(),
+        }
+    }
 }
 
 impl Widget for TextField {
@@ -28,37 +113,49 @@ impl Widget for TextField {
             panic!("unknown width");
         }
 
-        let unicode = UnicodeSegmentation::graphemes(self.content.as_str(), true);
+        let graphemes = self.display_graphemes();
 
-        let cursor_at_end = self.cursor == unicode.clone().count();
-        let mut spans = vec![Span::styled(
-            unicode
-                .clone()
-                .skip(self.scroll)
-                .take(self.cursor - self.scroll)
-                .collect::<String>(),
-            self.text_style,
-        )];
+        let mut spans = graphemes
+            .iter()
+            .enumerate()
+            .skip(self.scroll)
+            .map(|(index, grapheme)| Span::styled(grapheme.as_str(), self.grapheme_style(index)))
+            .collect::<Vec<_>>();
 
-        if cursor_at_end {
-            spans.push(Span::styled(String::from(' '), self.cursor_style));
-        } else {
+        if self.cursor == graphemes.len() {
             spans.push(Span::styled(
-                unicode
-                    .clone()
-                    .skip(self.cursor)
-                    .take(1)
-                    .collect::<String>(),
-                self.cursor_style,
-            ));
-            spans.push(Span::styled(
-                unicode.clone().skip(self.cursor + 1).collect::<String>(),
-                self.text_style,
+                String::from(' '),
+                self.grapheme_style(self.cursor),
             ));
         }
 
-        let paragraph = Paragraph::new(Line::from(spans)).style(self.style);
-        paragraph.render(area, buf);
+        let line = Line::from(spans);
+        let indicator_width = self.indicator_width();
+        let content_area = Rect {
+            x: area.x + indicator_width,
+            width: self
+                .editable_width(area.width)
+                .saturating_sub(indicator_width),
+            ..area
+        };
+
+        buf.set_style(area, self.style);
+        if indicator_width != 0 {
+            buf.set_string(area.x, area.y, "<", self.style);
+        }
+        if let Some(suffix) = &self.suffix {
+            let x = content_area.x + (line.width() as u16).min(content_area.width);
+            buf.set_stringn(
+                x,
+                area.y,
+                suffix,
+                area.right().saturating_sub(x) as usize,
+                self.suffix_style,
+            );
+        }
+
+        let paragraph = Paragraph::new(line).style(self.style);
+        paragraph.render(content_area, buf);
     }
 }
 
@@ -72,35 +169,93 @@ impl Default for TextField {
             text_style: Style::default(),
             cursor_style: Style::default().bg(Color::Gray),
             width: None,
+            mask: None,
+            reveal_last: false,
+            selection: None,
+            selection_style: Style::default().bg(Color::DarkGray),
+            highlight_range: None,
+            highlight_range_style: Style::default().fg(Color::Red),
+            suffix: None,
+            suffix_style: Style::default(),
+            validator: None,
+            on_backspace_empty: None,
+            cursor_visible: true,
+            multiline: false,
+            paste_newline_replace: Some(' '),
+            report_cursor: false,
+            trim_on_submit: false,
+            active: true,
+            inactive_cursor_style: Style::default(),
+            read_only: false,
+            scroll_indicator: false,
+            modified: false,
+            saved_content: String::default(),
         }
     }
 }
 
 impl TextField {
+    /// Insert a character before the grapheme at `index`
+    ///
+    /// If the character combines with the grapheme before it (such as a combining accent), it
+    /// becomes part of that grapheme and the cursor does not move. Characters that would combine
+    /// with the grapheme after it are rejected as that would split a grapheme cluster
     pub fn insert(&mut self, index: usize, c: char) -> Result<(), TextFieldError> {
-        self.content = format!(
-            "{}{}{}",
-            UnicodeSegmentation::graphemes(self.content.as_str(), true)
-                .take(index)
-                .collect::<String>(),
-            c,
-            UnicodeSegmentation::graphemes(self.content.as_str(), true)
-                .skip(index)
-                .collect::<String>()
-        );
-        self.cursor += 1;
+        self.insert_raw(index, c.encode_utf8(&mut [0; 4]))
+    }
+
+    /// Insert a string (such as pasted text), same as `insert()` but with multiple characters
+    ///
+    /// If `self.multiline` is `false`, newlines are replaced with `self.paste_newline_replace`
+    pub fn insert_str(&mut self, index: usize, s: &str) -> Result<(), TextFieldError> {
+        if self.multiline {
+            return self.insert_raw(index, s);
+        }
+
+        let s = s
+            .replace("\r\n", "\n")
+            .chars()
+            .filter_map(|c| match c {
+                '\n' | '\r' => self.paste_newline_replace,
+                c => Some(c),
+            })
+            .collect::<String>();
+        self.insert_raw(index, &s)
+    }
+
+    fn insert_raw(&mut self, index: usize, s: &str) -> Result<(), TextFieldError> {
+        if self.read_only {
+            return Ok(());
+        }
+
+        let graphemes =
+            UnicodeSegmentation::graphemes(self.content.as_str(), true).collect::<Vec<_>>();
+        let (before, after) = graphemes.split_at(index.min(graphemes.len()));
+
+        let content = format!("{}{}{}", before.concat(), s, after.concat());
+        let new_graphemes =
+            UnicodeSegmentation::graphemes(content.as_str(), true).collect::<Vec<_>>();
+
+        if !new_graphemes.ends_with(after) {
+            return Err(TextFieldError::MergesGrapheme);
+        }
+
+        self.cursor += new_graphemes.len() - graphemes.len();
+        self.content = content;
+        self.update_modified();
         self.update()?;
         Ok(())
     }
 
     pub fn remove(&mut self, index: usize) -> Result<(), TextFieldError> {
-        if self.cursor == 0 {
+        if self.cursor == 0 || self.read_only {
             return Ok(());
         }
         let s = self.content.clone();
         let mut s = UnicodeSegmentation::graphemes(s.as_str(), true).collect::<Vec<_>>();
         s.remove(index - 1);
         self.content = s.into_iter().collect();
+        self.update_modified();
         self.cursor -= 1;
         self.update()?;
         Ok(())
@@ -111,9 +266,60 @@ impl TextField {
     }
 
     pub fn pop(&mut self) -> Result<(), TextFieldError> {
+        if self.read_only {
+            return Ok(());
+        }
+
+        if self.cursor == 0 {
+            if let Some(on_backspace_empty) = &mut self.on_backspace_empty {
+                on_backspace_empty();
+            }
+        }
+
         self.remove(self.cursor)
     }
 
+    /// Remove the grapheme after the cursor without moving the cursor, does nothing at the end
+    pub fn delete_forward(&mut self) -> Result<(), TextFieldError> {
+        if self.read_only {
+            return Ok(());
+        }
+
+        let mut graphemes =
+            UnicodeSegmentation::graphemes(self.content.as_str(), true).collect::<Vec<_>>();
+        if self.cursor >= graphemes.len() {
+            return Ok(());
+        }
+
+        graphemes.remove(self.cursor);
+        self.content = graphemes.concat();
+        self.update_modified();
+        self.update()
+    }
+
+    /// Replace the content and treat it as saved, the cursor is moved to the end
+    pub fn set_content(&mut self, content: String) -> Result<(), TextFieldError> {
+        self.cursor = UnicodeSegmentation::graphemes(content.as_str(), true).count();
+        self.saved_content = content.clone();
+        self.content = content;
+        self.modified = false;
+        self.update()
+    }
+
+    /// Treat the current content as saved, `is_modified()` is `false` until the next change
+    pub fn mark_saved(&mut self) {
+        self.saved_content = self.content.clone();
+        self.modified = false;
+    }
+
+    pub fn is_modified(&self) -> bool {
+        self.modified
+    }
+
+    fn update_modified(&mut self) {
+        self.modified = self.content != self.saved_content;
+    }
+
     pub fn left(&mut self) -> Result<(), TextFieldError> {
         if self.cursor == 0 {
             return Ok(());
@@ -132,15 +338,171 @@ impl TextField {
         self.update()
     }
 
+    /// Move the cursor to the grapheme which starts at the display column nearest to `col`
+    /// (relative to the left of the field), returns the new cursor position
+    pub fn move_to_column(&mut self, col: u16) -> Result<usize, TextFieldError> {
+        let graphemes = self.display_graphemes();
+        let col = col.saturating_sub(self.indicator_width()) as usize;
+
+        let mut start: usize = 0;
+        let mut nearest = (self.scroll, usize::MAX);
+        for index in self.scroll..=graphemes.len() {
+            let distance = start.abs_diff(col);
+            if distance <= nearest.1 {
+                nearest = (index, distance);
+            }
+
+            match graphemes.get(index) {
+                Some(grapheme) => start += grapheme.width(),
+                None => break,
+            }
+        }
+
+        self.cursor = nearest.0;
+        self.update()?;
+        Ok(self.cursor)
+    }
+
+    /// Move the cursor to the start of the current line
     pub fn first(&mut self) -> Result<(), TextFieldError> {
-        self.cursor = 0;
+        let graphemes = UnicodeSegmentation::graphemes(self.content.as_str(), true)
+            .take(self.cursor)
+            .collect::<Vec<_>>();
+        self.cursor = graphemes
+            .iter()
+            .rposition(|grapheme| Self::is_newline(grapheme))
+            .map_or(0, |index| index + 1);
         self.update()
     }
 
+    /// Move the cursor to the end of the current line
     pub fn last(&mut self) -> Result<(), TextFieldError> {
-        self.cursor = self.content.len();
+        let graphemes = UnicodeSegmentation::graphemes(self.content.as_str(), true);
+        let len = graphemes.clone().count();
+        self.cursor = graphemes
+            .enumerate()
+            .skip(self.cursor)
+            .find(|(_, grapheme)| Self::is_newline(grapheme))
+            .map_or(len, |(index, _)| index);
+        self.update()
+    }
+
+    /// Move the cursor to the start of the content
+    pub fn doc_start(&mut self) -> Result<(), TextFieldError> {
+        self.cursor = 0;
+        self.update()
+    }
+
+    /// Move the cursor to the end of the content
+    pub fn doc_end(&mut self) -> Result<(), TextFieldError> {
+        self.cursor = UnicodeSegmentation::graphemes(self.content.as_str(), true).count();
         self.update()
     }
+
+    fn is_newline(grapheme: &str) -> bool {
+        grapheme == "\n" || grapheme == "\r\n"
+    }
+}
+
+impl TextField {
+    /// Handle a key event with the default key bindings
+    ///
+    /// * Characters are inserted at the cursor, Backspace removes the grapheme before the cursor
+    /// * Delete removes the grapheme after the cursor
+    /// * Left, Right, Home and End moves the cursor
+    /// * Ctrl+Home and Ctrl+End goes to the start and end of the content
+    /// * Enter submits the content after checking it with `self.validator`, the content is trimmed
+    ///   first if `self.trim_on_submit` is `true`
+    pub fn handle_key(&mut self, key: KeyEvent) -> Result<TextFieldAction, TextFieldError> {
+        match key.code {
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => self.push(c)?,
+            KeyCode::Backspace => self.pop()?,
+            KeyCode::Delete => self.delete_forward()?,
+            KeyCode::Left => self.left()?,
+            KeyCode::Right => self.right()?,
+            KeyCode::Home if key.modifiers.contains(KeyModifiers::CONTROL) => self.doc_start()?,
+            KeyCode::End if key.modifiers.contains(KeyModifiers::CONTROL) => self.doc_end()?,
+            KeyCode::Home => self.first()?,
+            KeyCode::End => self.last()?,
+            KeyCode::Enter => {
+                let content = if self.trim_on_submit {
+                    self.trimmed_content()
+                } else {
+                    self.content.clone()
+                };
+
+                return Ok(match &self.validator {
+                    Some(validator) => match validator(&content) {
+                        Ok(()) => TextFieldAction::Submit(content),
+                        Err(e) => TextFieldAction::SubmitRejected(e),
+                    },
+                    None => TextFieldAction::Submit(content),
+                });
+            }
+            _ => return Ok(TextFieldAction::Ignored),
+        }
+
+        Ok(TextFieldAction::Handled)
+    }
+}
+
+impl TextField {
+    /// Number of words in `self.content`
+    pub fn word_count(&self) -> usize {
+        self.content.unicode_words().count()
+    }
+
+    /// The word the cursor is in or right next to
+    pub fn current_word(&self) -> Option<String> {
+        let cursor = self.byte_index(self.cursor);
+
+        self.content
+            .split_word_bound_indices()
+            .filter(|(_, word)| word.chars().any(char::is_alphanumeric))
+            .find(|(start, word)| *start <= cursor && cursor <= start + word.len())
+            .map(|(_, word)| word.to_string())
+    }
+
+    /// `self.content` without whitespace at both ends
+    pub fn trimmed_content(&self) -> String {
+        self.content.trim().to_string()
+    }
+
+    /// Rows needed to show the content wrapped at `width` columns, graphemes are not split and
+    /// newlines start a new row. This does not change how the field is rendered
+    pub fn wrapped_height(&self, width: u16) -> u16 {
+        if width == 0 {
+            return 0;
+        }
+
+        let mut rows: u16 = 1;
+        let mut used = 0;
+        for grapheme in self.display_graphemes() {
+            if Self::is_newline(&grapheme) {
+                rows = rows.saturating_add(1);
+                used = 0;
+                continue;
+            }
+
+            let grapheme_width = grapheme.width();
+            if used + grapheme_width > width as usize && used != 0 {
+                rows = rows.saturating_add(1);
+                used = 0;
+            }
+            used += grapheme_width;
+        }
+
+        rows
+    }
+
+    /// Byte index of the grapheme at `index`, or the length of `self.content` if out of bound
+    fn byte_index(&self, index: usize) -> usize {
+        self.content
+            .grapheme_indices(true)
+            .nth(index)
+            .map(|(byte_index, _)| byte_index)
+            .unwrap_or(self.content.len())
+    }
 }
 
 impl TextField {
@@ -148,6 +510,137 @@ impl TextField {
         self.width = Some(width)
     }
 
+    pub fn set_mask(&mut self, mask: Option<char>) {
+        self.mask = mask
+    }
+
+    pub fn set_reveal(&mut self, reveal_last: bool) {
+        self.reveal_last = reveal_last
+    }
+
+    /// Select graphemes from `start` (inclusive) to `end` (exclusive)
+    pub fn set_selection(&mut self, selection: Option<(usize, usize)>) {
+        self.selection = selection.map(|(start, end)| (start.min(end), start.max(end)))
+    }
+
+    pub fn set_selection_style(&mut self, selection_style: Style) {
+        self.selection_style = selection_style
+    }
+
+    pub fn set_highlight_range(&mut self, highlight_range: Option<(usize, usize)>) {
+        self.highlight_range = highlight_range;
+    }
+
+    pub fn set_highlight_range_style(&mut self, highlight_range_style: Style) {
+        self.highlight_range_style = highlight_range_style;
+    }
+
+    pub fn set_suffix(&mut self, suffix: Option<String>) {
+        self.suffix = suffix
+    }
+
+    pub fn set_suffix_style(&mut self, suffix_style: Style) {
+        self.suffix_style = suffix_style
+    }
+
+    pub fn set_cursor_visible(&mut self, cursor_visible: bool) {
+        self.cursor_visible = cursor_visible;
+    }
+
+    pub fn set_multiline(&mut self, multiline: bool) {
+        self.multiline = multiline;
+    }
+
+    pub fn set_paste_newline_replace(&mut self, paste_newline_replace: Option<char>) {
+        self.paste_newline_replace = paste_newline_replace;
+    }
+
+    pub fn set_report_cursor(&mut self, report_cursor: bool) {
+        self.report_cursor = report_cursor;
+    }
+
+    pub fn set_scroll_indicator(&mut self, scroll_indicator: bool) {
+        self.scroll_indicator = scroll_indicator;
+    }
+
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+
+    pub fn set_active(&mut self, active: bool) {
+        self.active = active;
+    }
+
+    pub fn set_inactive_cursor_style(&mut self, inactive_cursor_style: Style) {
+        self.inactive_cursor_style = inactive_cursor_style;
+    }
+
+    pub fn set_trim_on_submit(&mut self, trim_on_submit: bool) {
+        self.trim_on_submit = trim_on_submit;
+    }
+
+    pub fn set_validator<F: Fn(&str) -> Result<(), String> + 'static>(&mut self, validator: F) {
+        self.validator = Some(Rc::new(validator))
+    }
+
+    pub fn set_on_backspace_empty<F: FnMut() + 'static>(&mut self, on_backspace_empty: F) {
+        self.on_backspace_empty = Some(Box::new(on_backspace_empty))
+    }
+
+    /// Columns taken by the scroll indicator
+    fn indicator_width(&self) -> u16 {
+        u16::from(self.scroll_indicator && self.scroll > 0)
+    }
+
+    /// Width avaliable for the content and the cursor, which excludes the suffix
+    fn editable_width(&self, width: u16) -> u16 {
+        let suffix_width = self
+            .suffix
+            .as_ref()
+            .map_or(0, |suffix| suffix.width() as u16);
+        width.saturating_sub(suffix_width)
+    }
+
+    /// Style of the grapheme at `index` when rendered
+    fn grapheme_style(&self, index: usize) -> Style {
+        if index == self.cursor && self.cursor_visible && !self.report_cursor && !self.read_only {
+            if self.active {
+                return self.cursor_style;
+            }
+            if self.inactive_cursor_style != Style::default() {
+                return self.inactive_cursor_style;
+            }
+        }
+
+        match (self.selection, self.highlight_range) {
+            (Some((start, end)), _) if (start..end).contains(&index) => self.selection_style,
+            (_, Some((start, end))) if (start..end).contains(&index) => self.highlight_range_style,
+            _ => self.text_style,
+        }
+    }
+
+    /// Graphemes of `self.content` as they should be displayed, with `self.mask` applied
+    fn display_graphemes(&self) -> Vec<String> {
+        let graphemes = UnicodeSegmentation::graphemes(self.content.as_str(), true);
+
+        let mask = match self.mask {
+            Some(mask) => mask,
+            None => return graphemes.map(String::from).collect(),
+        };
+
+        let len = graphemes.clone().count();
+        graphemes
+            .enumerate()
+            .map(|(index, grapheme)| {
+                if self.reveal_last && index + 1 == len {
+                    grapheme.to_string()
+                } else {
+                    mask.to_string()
+                }
+            })
+            .collect()
+    }
+
     pub fn update(&mut self) -> Result<(), TextFieldError> {
         let width = if let Some(width) = self.width {
             width
@@ -155,10 +648,26 @@ impl TextField {
             return Err(TextFieldError::UnknownWidth);
         };
 
+        self.update_with_width(width);
+        Ok(())
+    }
+
+    /// Same as `update()`, but with `width` instead of `self.width`
+    fn update_with_width(&mut self, width: u16) {
+        let full_width = self.editable_width(width).max(1);
+        // one column less when scrolled if there is a scroll indicator
+        let width = |scroll: usize| {
+            if self.scroll_indicator && scroll > 0 {
+                (full_width - 1).max(1) as usize
+            } else {
+                full_width as usize
+            }
+        };
+
         if self.scroll > self.cursor {
             self.scroll = self.cursor;
-        } else if self.scroll + width as usize - 1 < self.cursor {
-            self.scroll = self.cursor - width as usize + 1;
+        } else if self.scroll + width(self.scroll) - 1 < self.cursor {
+            self.scroll = self.cursor - width(1) + 1;
         }
 
         let len = UnicodeSegmentation::graphemes(self.content.as_str(), true).count();
@@ -166,14 +675,61 @@ impl TextField {
         if self.cursor > len {
             self.cursor = len;
         }
+    }
 
-        Ok(())
+    /// Position of the cursor on screen when rendered in `area`, for use with
+    /// `frame.set_cursor_position()`, `None` if the cursor is not in `area`
+    pub fn cursor_screen_pos(&self, area: Rect) -> Option<(u16, u16)> {
+        let offset = self
+            .display_graphemes()
+            .iter()
+            .take(self.cursor)
+            .skip(self.scroll)
+            .map(|grapheme| grapheme.width())
+            .sum::<usize>();
+
+        let indicator_width = self.indicator_width();
+        let width = self
+            .editable_width(area.width)
+            .saturating_sub(indicator_width);
+        if offset >= width as usize || area.height == 0 {
+            return None;
+        }
+
+        Some((area.x + indicator_width + offset as u16, area.y))
     }
+
+    /// Render into `area` with `area.width` as the width, `self.width` is ignored and the scroll
+    /// is updated to fit the cursor in `area`
+    pub fn render_at(&self, area: Rect, buf: &mut Buffer) {
+        let mut textfield = self.clone();
+        textfield.width = Some(area.width);
+        textfield.update_with_width(area.width);
+        textfield.render(area, buf);
+    }
+}
+
+/// Validator for `TextField.validator`
+pub type TextFieldValidator = Rc<dyn Fn(&str) -> Result<(), String>>;
+
+/// Returned by `TextField::handle_key()`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TextFieldAction {
+    /// The key is used to edit the content or move the cursor
+    Handled,
+    /// The key is not used by the text field
+    Ignored,
+    /// Enter is pressed and the content passed validation
+    Submit(String),
+    /// Enter is pressed but the content is rejected by the validator
+    SubmitRejected(String),
 }
 
 #[derive(Debug)]
 pub enum TextFieldError {
     UnknownWidth,
+    /// The inserted character would merge with the grapheme after it
+    MergesGrapheme,
 }
 
 impl Display for TextFieldError {
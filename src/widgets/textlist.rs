@@ -1,11 +1,14 @@
-use std::{error::Error, fmt::Display};
+use std::{collections::HashSet, error::Error, fmt::Display};
 
 use ratatui::{
+    buffer::Buffer,
     layout::Rect,
     style::Style,
+    text::Line,
     widgets::{Block, BorderType, Borders, Paragraph, Widget},
 };
 use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
 /// A widget for selecting from a list of items
 ///
@@ -15,15 +18,14 @@ use unicode_segmentation::UnicodeSegmentation;
 /// The requirement for the text list widget to render are:
 /// * Minimal height of 3
 /// * Height should be updated with `self.set_height()` before rendering
-
-#[derive(Clone)]
 pub struct TextList {
     /// Items that are in the list, set by `.items()` or `.set_items()` function
     pub items: Vec<String>,
     /// The selected item, should be updated using provided functions. `0` should be the first item
     pub selected: usize,
     /// How many items to scroll down from the first item, should auto update if `selected` is
-    /// changed with provided functions.
+    /// changed with provided functions. `scroll_up()` and `scroll_down()` only change this, so the
+    /// selected item can be off screen until the next `update()`
     pub scroll: usize,
     /// The style of the entire text list including unselected (normal) items
     pub style: Style,
@@ -37,37 +39,324 @@ pub struct TextList {
     pub height: Option<u16>,
     /// Only allow ASCII characters to prevent unicode length issues
     pub ascii_only: bool,
-    /// Character to replace non ASCII characters with, only useful when `ascii_only` is `true`
+    /// Character to replace non ASCII graphemes with, only useful when `ascii_only` is `true`
     pub non_ascii_replace: char,
     /// How to handle items that got a longer length than the width which the widget can render
     pub trim_type: TrimType,
+    /// If the list is focused, the selected item is only drawn in a cursor box when focused
+    pub focused: bool,
+    /// Show `loading_text` instead of the items, movement functions do nothing when loading
+    pub loading: bool,
+    /// Text to show when `loading` is `true`
+    pub loading_text: String,
+    /// Style of `loading_text`
+    pub loading_style: Style,
+    /// Show `▲` and `▼` on the last column when there are more items above or below the screen
+    pub overflow_markers: bool,
+    /// Style of the overflow markers
+    pub overflow_marker_style: Style,
+    /// Pending key for multi key commands in `self.vim_key()`
+    pub pending: Option<char>,
+    /// Pending numeric prefix for `self.vim_key()`
+    pub pending_count: Option<usize>,
+    /// Keep the selected item in the middle of the list, except near the start and end
+    pub center_selection: bool,
+    /// Rows kept on screen when going up or down a page, smaller values jump further
+    pub page_overlap: u16,
+    /// Indices of items which have a separator row drawn after them
+    pub separators_after: HashSet<usize>,
+    /// Symbol repeated to draw a separator row
+    pub separator_symbol: char,
+    /// Style of the separator rows
+    pub separator_style: Style,
+    /// When the height is less than 3, draw the selected item without a box instead of nothing
+    pub compact_fallback: bool,
+    /// Replace newlines with spaces, expand tabs and remove other control characters from items
+    /// when they are set, so every item stays on one row
+    pub sanitize: bool,
+    /// Number of spaces a tab expands to, only useful when `sanitize` is `true`
+    pub tab_width: usize,
+    /// Draw only the selected item and a `▾` marker on the first row like a combobox, `height` is
+    /// not checked
+    pub collapsed: bool,
+    /// Called by `down()` and `pagedown()` when the selected item is one of the last
+    /// `near_end_threshold` items, the returned items are added to the end of the list. Reset to
+    /// `None` when cloned
+    pub on_near_end: Option<NearEndCallback>,
+    /// How close to the end the selected item has to be for `on_near_end` to be called
+    pub near_end_threshold: usize,
+    /// Draw a scrollbar on the last column when some items are not on screen, the column is
+    /// always kept for the scrollbar
+    pub scrollbar: bool,
+    /// Title on the top border of the cursor box
+    pub cursor_title: Option<String>,
+    /// Show the position of the selected item such as `3/10` as the cursor box title, unless
+    /// `cursor_title` is set
+    pub show_position_title: bool,
+    /// If items longer than the width are trimmed by `trim_type` or wrapped to more rows
+    pub wrap_mode: WrapMode,
+    /// Width of the widget, needed for `WrapMode::Wrap`
+    pub width: Option<u16>,
+}
+
+impl Clone for TextList {
+    fn clone(&self) -> Self {
+        Self {
+            items: self.items.clone(),
+            selected: self.selected,
+            scroll: self.scroll,
+            style: self.style,
+            cursor_style: self.cursor_style,
+            selected_style: self.selected_style,
+            border_type: self.border_type,
+            height: self.height,
+            ascii_only: self.ascii_only,
+            non_ascii_replace: self.non_ascii_replace,
+            trim_type: self.trim_type,
+            focused: self.focused,
+            loading: self.loading,
+            loading_text: self.loading_text.clone(),
+            loading_style: self.loading_style,
+            overflow_markers: self.overflow_markers,
+            overflow_marker_style: self.overflow_marker_style,
+            pending: self.pending,
+            pending_count: self.pending_count,
+            center_selection: self.center_selection,
+            page_overlap: self.page_overlap,
+            separators_after: self.separators_after.clone(),
+            separator_symbol: self.separator_symbol,
+            separator_style: self.separator_style,
+            compact_fallback: self.compact_fallback,
+            sanitize: self.sanitize,
+            tab_width: self.tab_width,
+            collapsed: self.collapsed,
+            on_near_end: None,
+            near_end_threshold: self.near_end_threshold,
+            scrollbar: self.scrollbar,
+            cursor_title: self.cursor_title.clone(),
+            show_position_title: self.show_position_title,
+            wrap_mode: self.wrap_mode,
+            width: self.width,
+        }
+    }
 }
 
 /// Movement related functions
 impl TextList {
     /// Should run this function after `scoll` of `selected` is updated to ensure that the cursor
-    /// is on screen
+    /// is on screen, this also scrolls back to the selected item if it is off screen after
+    /// `scroll_up()` or `scroll_down()`
     pub fn update(&mut self) -> Result<(), TextListError> {
+        let height = self.visible_height()?;
+
+        if self.center_selection {
+            self.scroll = self
+                .selected
+                .saturating_sub((height - 1) / 2)
+                .min(self.items.len().saturating_sub(height));
+        } else if self.selected < self.scroll {
+            self.scroll = self.selected;
+        }
+
+        // every item takes at least 1 row, so skip the ones that cannot fit, an item taller than
+        // the list is clipped rather than scrolled past
+        self.scroll = self.scroll.max((self.selected + 1).saturating_sub(height));
+        while self.scroll < self.selected && !self.fits(self.scroll, self.selected, height) {
+            self.scroll += 1;
+        }
+        Ok(())
+    }
+
+    /// Scroll so that the item at `index` is on screen without changing `selected`
+    pub fn ensure_visible(&mut self, index: usize) -> Result<(), TextListError> {
+        let height = self.visible_height()?;
+        let index = index.min(self.items.len().saturating_sub(1));
+
+        if index < self.scroll {
+            self.scroll = index;
+        }
+
+        self.scroll = self.scroll.max((index + 1).saturating_sub(height));
+        while self.scroll < index && !self.fits(self.scroll, index, height) {
+            self.scroll += 1;
+        }
+        Ok(())
+    }
+
+    /// If items from `from` to `to` (inclusive) and the separators between them fit in `height`
+    /// rows
+    fn fits(&self, from: usize, to: usize, height: usize) -> bool {
+        let separators = self
+            .separators_after
+            .iter()
+            .filter(|index| (from..to).contains(index))
+            .count();
+        let rows = match self.wrap_mode {
+            WrapMode::Truncate => to.saturating_sub(from) + 1,
+            WrapMode::Wrap => (from..=to.max(from))
+                .map(|index| self.item_rows(index))
+                .sum(),
+        };
+
+        rows + separators <= height
+    }
+
+    /// Rows taken by the item at `index` without the cursor box, more than 1 only if it is
+    /// wrapped
+    fn item_rows(&self, index: usize) -> usize {
+        match (self.wrap_mode, self.wrap_width(), self.items.get(index)) {
+            (WrapMode::Wrap, Some(width), Some(item)) => {
+                Self::wrap(&self.display_item(item), width).len()
+            }
+            _ => 1,
+        }
+    }
+
+    /// Width avaliable for wrapping items, which is `self.width` without the borders and the
+    /// scrollbar
+    fn wrap_width(&self) -> Option<usize> {
+        self.width
+            .map(|width| width.saturating_sub(2 + u16::from(self.scrollbar)) as usize)
+    }
+
+    /// Split `item` into lines at most `width` wide, graphemes are not split
+    fn wrap(item: &str, width: usize) -> Vec<String> {
+        let mut lines = vec![String::new()];
+        let mut used = 0;
+        for grapheme in UnicodeSegmentation::graphemes(item, true) {
+            let grapheme_width = grapheme.width();
+            if used + grapheme_width > width && used != 0 {
+                lines.push(String::new());
+                used = 0;
+            }
+            lines.last_mut().unwrap().push_str(grapheme);
+            used += grapheme_width;
+        }
+        lines
+    }
+
+    /// `item` as it is drawn, with non ASCII graphemes replaced if `self.ascii_only` is `true`
+    fn display_item(&self, item: &str) -> String {
+        if !self.ascii_only {
+            return item.to_string();
+        }
+
+        // replaced by grapheme so a cluster of many code points becomes 1 character
+        UnicodeSegmentation::graphemes(item, true)
+            .map(|grapheme| {
+                if grapheme.is_ascii() {
+                    grapheme.to_string()
+                } else {
+                    self.non_ascii_replace.to_string()
+                }
+            })
+            .collect()
+    }
+
+    /// `item` with newlines replaced by spaces, tabs expanded and other control characters removed
+    fn sanitized(item: &str, tab_width: usize) -> String {
+        item.replace("\r\n", "\n")
+            .chars()
+            .flat_map(|c| match c {
+                '\n' | '\r' => vec![' '],
+                '\t' => vec![' '; tab_width],
+                c if c.is_control() => Vec::new(),
+                c => vec![c],
+            })
+            .collect()
+    }
+
+    /// `item` trimmed with `self.trim_type` if it is longer than `width`
+    fn trimmed(&self, item: String, width: usize) -> String {
+        let end_with = self.trim_type.end_with();
+        let width_after = width.saturating_sub(end_with.chars().count());
+
+        if matches!(self.trim_type, TrimType::LeadingEllipsis) {
+            if item.width() > width {
+                return format!("{}{}", end_with, Self::tail(&item, width_after));
+            }
+            return item;
+        }
+
+        let chars = UnicodeSegmentation::graphemes(item.as_str(), true).collect::<Vec<_>>();
+        if chars.len() > width {
+            format!(
+                "{}{}",
+                chars.into_iter().take(width_after).collect::<String>(),
+                end_with
+            )
+        } else {
+            item
+        }
+    }
+
+    /// The longest end of `item` which is at most `width` wide
+    fn tail(item: &str, width: usize) -> String {
+        let mut used = 0;
+        let mut graphemes = UnicodeSegmentation::graphemes(item, true)
+            .rev()
+            .take_while(|grapheme| {
+                used += grapheme.width();
+                used <= width
+            })
+            .collect::<Vec<_>>();
+        graphemes.reverse();
+        graphemes.concat()
+    }
+
+    /// Number of items starting from `self.scroll` that can be shown in `height` rows, the first
+    /// item is always counted even if it is clipped
+    fn visible_count(&self, height: usize) -> usize {
+        (self.scroll..self.items.len())
+            .take_while(|index| *index == self.scroll || self.fits(self.scroll, *index, height))
+            .count()
+    }
+
+    /// Rows taken by the selected item, 3 when drawn in a cursor box and 1 when the list is not
+    /// focused or empty
+    pub fn selected_box_height(&self) -> u16 {
+        if self.focused && !self.items.is_empty() {
+            3
+        } else {
+            1
+        }
+    }
+
+    /// Rows needed to show `count` items with one of them selected, separators are not counted
+    pub fn rows_for(&self, count: usize) -> u16 {
+        if count == 0 {
+            return 0;
+        }
+
+        (count as u16 - 1).saturating_add(self.selected_box_height())
+    }
+
+    /// Number of items that can be shown on screen
+    fn visible_height(&self) -> Result<usize, TextListError> {
         let height = if let Some(h) = self.height {
-            h as i32 - 2
+            h
         } else {
             return Err(TextListError::UnknownHeight);
         };
 
-        if height <= 0 {
+        if height < 3 {
             return Err(TextListError::NotEnoughHeight);
         }
 
-        if self.selected < self.scroll {
-            self.scroll = self.selected;
-        } else if self.scroll + height as usize <= self.selected {
-            self.scroll = self.selected - height as usize + 1;
-        }
-        Ok(())
+        Ok(self.item_rows_in(height))
+    }
+
+    /// Rows left for items in `height` rows, the extra rows taken by the cursor box are excluded
+    fn item_rows_in(&self, height: u16) -> usize {
+        (height as usize + 1).saturating_sub(self.selected_box_height() as usize)
     }
 
     /// Move cursor up by 1 item (if there is)
     pub fn up(&mut self) -> Result<(), TextListError> {
+        if self.loading || self.items.is_empty() {
+            return Ok(());
+        }
+
         if self.selected != 0 {
             self.selected -= 1;
             self.update()?;
@@ -77,7 +366,7 @@ impl TextList {
 
     /// Move cursor down by 1 item (if there is)
     pub fn down(&mut self) -> Result<(), TextListError> {
-        if self.items.is_empty() {
+        if self.loading || self.items.is_empty() {
             return Ok(());
         }
 
@@ -85,11 +374,44 @@ impl TextList {
             self.selected += 1;
             self.update()?;
         }
+        self.load_near_end();
+        Ok(())
+    }
+
+    /// Scroll up by `n` items without changing `selected`, such as for mouse wheel scrolling
+    pub fn scroll_up(&mut self, n: usize) -> Result<(), TextListError> {
+        if self.loading {
+            return Ok(());
+        }
+
+        self.scroll = self.scroll.saturating_sub(n);
+        Ok(())
+    }
+
+    /// Scroll down by `n` items without changing `selected`, stops when the last item is at the
+    /// bottom
+    pub fn scroll_down(&mut self, n: usize) -> Result<(), TextListError> {
+        if self.loading {
+            return Ok(());
+        }
+
+        let height = self.visible_height()?;
+        let last = self.items.len().saturating_sub(1);
+        let mut max_scroll = last;
+        while max_scroll > 0 && self.fits(max_scroll - 1, last, height) {
+            max_scroll -= 1;
+        }
+
+        self.scroll = self.scroll.saturating_add(n).min(max_scroll);
         Ok(())
     }
 
     /// Go up 1 page without changing the cursor position on screen
     pub fn pageup(&mut self) -> Result<(), TextListError> {
+        if self.loading || self.items.is_empty() {
+            return Ok(());
+        }
+
         let height = match self.height {
             Some(h) => h as usize,
             None => return Err(TextListError::UnknownHeight),
@@ -99,9 +421,16 @@ impl TextList {
             return Ok(());
         }
 
-        let shift_by = height - 2;
+        let shift_by = height.saturating_sub(self.page_overlap as usize).max(1);
 
-        if self.selected < shift_by {
+        if self.wrap_mode == WrapMode::Wrap {
+            // move by rows instead of items as items can take more than 1 row
+            let mut rows = 0;
+            while self.selected > 0 && rows < shift_by {
+                self.selected -= 1;
+                rows += self.item_rows(self.selected);
+            }
+        } else if self.selected < shift_by {
             self.selected = 0;
         } else {
             self.selected -= shift_by;
@@ -120,18 +449,29 @@ impl TextList {
 
     /// Go down 1 page without changing the cursor position on screen
     pub fn pagedown(&mut self) -> Result<(), TextListError> {
+        if self.loading || self.items.is_empty() {
+            return Ok(());
+        }
+
         let height = match self.height {
             Some(h) => h as usize,
             None => return Err(TextListError::UnknownHeight),
         };
 
         if self.selected >= self.items.len() - 1 {
+            self.load_near_end();
             return Ok(());
         }
 
-        let shift_by = height - 2;
+        let shift_by = height.saturating_sub(self.page_overlap as usize).max(1);
 
-        if self.selected + shift_by > self.items.len() - 1 {
+        if self.wrap_mode == WrapMode::Wrap {
+            let mut rows = 0;
+            while self.selected < self.items.len() - 1 && rows < shift_by {
+                rows += self.item_rows(self.selected);
+                self.selected += 1;
+            }
+        } else if self.selected + shift_by > self.items.len() - 1 {
             self.selected = self.items.len() - 1;
         } else {
             self.selected += shift_by;
@@ -139,17 +479,39 @@ impl TextList {
             if self.scroll + shift_by + height - 2 < self.items.len() {
                 self.scroll += shift_by;
             } else {
-                self.scroll = self.items.len() - 1 - height + 2;
+                self.scroll = (self.items.len() + 1).saturating_sub(height);
             }
         }
 
         self.update()?;
+        self.load_near_end();
 
         Ok(())
     }
 
+    /// Call `self.on_near_end` and add the items it returns if the selected item is near the end
+    fn load_near_end(&mut self) {
+        if self.items.len() - self.selected > self.near_end_threshold {
+            return;
+        }
+
+        if let Some(on_near_end) = &mut self.on_near_end {
+            let items = on_near_end();
+            let sanitize = self.sanitize.then_some(self.tab_width);
+            self.items
+                .extend(items.into_iter().map(|item| match sanitize {
+                    Some(tab_width) => Self::sanitized(&item, tab_width),
+                    None => item,
+                }));
+        }
+    }
+
     /// Go to the first item
     pub fn first(&mut self) -> Result<(), TextListError> {
+        if self.loading || self.items.is_empty() {
+            return Ok(());
+        }
+
         if self.selected == 0 {
             return Ok(());
         }
@@ -161,6 +523,10 @@ impl TextList {
 
     /// Go to the last item
     pub fn last(&mut self) -> Result<(), TextListError> {
+        if self.loading || self.items.is_empty() {
+            return Ok(());
+        }
+
         if self.selected == self.items.len() - 1 {
             return Ok(());
         }
@@ -169,6 +535,80 @@ impl TextList {
         self.update()?;
         Ok(())
     }
+
+    /// Go to the next item starting with `c` (case insensitive) after the selected item, wrapping
+    /// to the start, returns `true` if an item is found
+    pub fn jump_to_char(&mut self, c: char) -> Result<bool, TextListError> {
+        if self.loading || self.items.is_empty() {
+            return Ok(false);
+        }
+
+        let c = c.to_lowercase().collect::<String>();
+        let len = self.items.len();
+        let found = (1..=len)
+            .map(|offset| (self.selected + offset) % len)
+            .find(|index| self.items[*index].to_lowercase().starts_with(&c));
+
+        match found {
+            Some(index) => {
+                self.selected = index;
+                self.update()?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+}
+
+/// Vim style key bindings
+impl TextList {
+    /// Handle a vim style key, returns `true` if the key is consumed
+    ///
+    /// * `gg` goes to the first item, `G` goes to the last item
+    /// * `j` and `k` moves down and up
+    /// * Numeric prefixes are supported, such as `3j` or `5gg` (goes to the 5th item)
+    pub fn vim_key(&mut self, c: char) -> Result<bool, TextListError> {
+        if let Some(digit) = c.to_digit(10) {
+            if digit != 0 || self.pending_count.is_some() {
+                self.pending = None;
+                self.pending_count = Some(
+                    self.pending_count
+                        .unwrap_or(0)
+                        .saturating_mul(10)
+                        .saturating_add(digit as usize),
+                );
+                return Ok(true);
+            }
+        }
+
+        let pending = self.pending.take();
+        let count = self.pending_count.take();
+
+        match (pending, c) {
+            (None, 'g') => {
+                self.pending = Some('g');
+                self.pending_count = count;
+            }
+            (Some('g'), 'g') | (_, 'G') => match count {
+                Some(count) => self.set_selected(count.min(self.items.len()).saturating_sub(1))?,
+                None if c == 'g' => self.first()?,
+                None => self.last()?,
+            },
+            (_, 'j') => {
+                for _ in 0..count.unwrap_or(1) {
+                    self.down()?;
+                }
+            }
+            (_, 'k') => {
+                for _ in 0..count.unwrap_or(1) {
+                    self.up()?;
+                }
+            }
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
 }
 
 /// Setters
@@ -203,6 +643,186 @@ impl TextList {
         self.cursor_style = cursor_style;
     }
 
+    pub fn focused(mut self, focused: bool) -> Self {
+        self.set_focused(focused);
+        self
+    }
+
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    pub fn loading(mut self, loading: bool) -> Self {
+        self.set_loading(loading);
+        self
+    }
+
+    pub fn set_loading(&mut self, loading: bool) {
+        self.loading = loading;
+    }
+
+    pub fn loading_text(mut self, loading_text: String) -> Self {
+        self.set_loading_text(loading_text);
+        self
+    }
+
+    pub fn set_loading_text(&mut self, loading_text: String) {
+        self.loading_text = loading_text;
+    }
+
+    pub fn loading_style(mut self, loading_style: Style) -> Self {
+        self.set_loading_style(loading_style);
+        self
+    }
+
+    pub fn set_loading_style(&mut self, loading_style: Style) {
+        self.loading_style = loading_style;
+    }
+
+    pub fn overflow_markers(mut self, overflow_markers: bool) -> Self {
+        self.set_overflow_markers(overflow_markers);
+        self
+    }
+
+    pub fn set_overflow_markers(&mut self, overflow_markers: bool) {
+        self.overflow_markers = overflow_markers;
+    }
+
+    pub fn overflow_marker_style(mut self, overflow_marker_style: Style) -> Self {
+        self.set_overflow_marker_style(overflow_marker_style);
+        self
+    }
+
+    pub fn set_overflow_marker_style(&mut self, overflow_marker_style: Style) {
+        self.overflow_marker_style = overflow_marker_style;
+    }
+
+    pub fn center_selection(mut self, center_selection: bool) -> Self {
+        self.set_center_selection(center_selection);
+        self
+    }
+
+    pub fn set_center_selection(&mut self, center_selection: bool) {
+        self.center_selection = center_selection;
+    }
+
+    pub fn page_overlap(mut self, page_overlap: u16) -> Self {
+        self.set_page_overlap(page_overlap);
+        self
+    }
+
+    pub fn set_page_overlap(&mut self, page_overlap: u16) {
+        self.page_overlap = page_overlap;
+    }
+
+    pub fn separators_after(mut self, separators_after: HashSet<usize>) -> Self {
+        self.set_separators_after(separators_after);
+        self
+    }
+
+    pub fn set_separators_after(&mut self, separators_after: HashSet<usize>) {
+        self.separators_after = separators_after;
+    }
+
+    pub fn separator_symbol(mut self, separator_symbol: char) -> Self {
+        self.set_separator_symbol(separator_symbol);
+        self
+    }
+
+    pub fn set_separator_symbol(&mut self, separator_symbol: char) {
+        self.separator_symbol = separator_symbol;
+    }
+
+    pub fn separator_style(mut self, separator_style: Style) -> Self {
+        self.set_separator_style(separator_style);
+        self
+    }
+
+    pub fn set_separator_style(&mut self, separator_style: Style) {
+        self.separator_style = separator_style;
+    }
+
+    pub fn compact_fallback(mut self, compact_fallback: bool) -> Self {
+        self.set_compact_fallback(compact_fallback);
+        self
+    }
+
+    pub fn set_compact_fallback(&mut self, compact_fallback: bool) {
+        self.compact_fallback = compact_fallback;
+    }
+
+    pub fn sanitize(mut self, sanitize: bool) -> Self {
+        self.set_sanitize(sanitize);
+        self
+    }
+
+    pub fn set_sanitize(&mut self, sanitize: bool) {
+        self.sanitize = sanitize;
+    }
+
+    pub fn tab_width(mut self, tab_width: usize) -> Self {
+        self.set_tab_width(tab_width);
+        self
+    }
+
+    pub fn set_tab_width(&mut self, tab_width: usize) {
+        self.tab_width = tab_width;
+    }
+
+    pub fn collapsed(mut self, collapsed: bool) -> Self {
+        self.set_collapsed(collapsed);
+        self
+    }
+
+    pub fn set_collapsed(&mut self, collapsed: bool) {
+        self.collapsed = collapsed;
+    }
+
+    pub fn cursor_title(mut self, cursor_title: Option<String>) -> Self {
+        self.set_cursor_title(cursor_title);
+        self
+    }
+
+    pub fn set_cursor_title(&mut self, cursor_title: Option<String>) {
+        self.cursor_title = cursor_title;
+    }
+
+    pub fn show_position_title(mut self, show_position_title: bool) -> Self {
+        self.set_show_position_title(show_position_title);
+        self
+    }
+
+    pub fn set_show_position_title(&mut self, show_position_title: bool) {
+        self.show_position_title = show_position_title;
+    }
+
+    pub fn scrollbar(mut self, scrollbar: bool) -> Self {
+        self.set_scrollbar(scrollbar);
+        self
+    }
+
+    pub fn set_scrollbar(&mut self, scrollbar: bool) {
+        self.scrollbar = scrollbar;
+    }
+
+    pub fn on_near_end<F: FnMut() -> Vec<String> + 'static>(mut self, on_near_end: F) -> Self {
+        self.set_on_near_end(on_near_end);
+        self
+    }
+
+    pub fn set_on_near_end<F: FnMut() -> Vec<String> + 'static>(&mut self, on_near_end: F) {
+        self.on_near_end = Some(Box::new(on_near_end));
+    }
+
+    pub fn near_end_threshold(mut self, near_end_threshold: usize) -> Self {
+        self.set_near_end_threshold(near_end_threshold);
+        self
+    }
+
+    pub fn set_near_end_threshold(&mut self, near_end_threshold: usize) {
+        self.near_end_threshold = near_end_threshold;
+    }
+
     pub fn height(mut self, height: u16) -> Self {
         self.set_height(height);
         self
@@ -212,13 +832,56 @@ impl TextList {
         self.height = Some(height);
     }
 
+    pub fn width(mut self, width: u16) -> Self {
+        self.set_width(width);
+        self
+    }
+
+    pub fn set_width(&mut self, width: u16) {
+        self.width = Some(width);
+    }
+
+    pub fn wrap_mode(mut self, wrap_mode: WrapMode) -> Self {
+        self.set_wrap_mode(wrap_mode);
+        self
+    }
+
+    pub fn set_wrap_mode(&mut self, wrap_mode: WrapMode) {
+        self.wrap_mode = wrap_mode;
+    }
+
     pub fn items<D: Display>(mut self, items: &[D]) -> Result<Self, Box<dyn Error>> {
         self.set_items(items)?;
         Ok(self)
     }
 
     pub fn set_items<D: Display>(&mut self, items: &[D]) -> Result<(), Box<dyn Error>> {
-        self.items = items.iter().map(|item| format!("{}", item)).collect();
+        self.set_items_iter(items)?;
+        Ok(())
+    }
+
+    pub fn items_iter<I: IntoIterator<Item = D>, D: Display>(
+        mut self,
+        iter: I,
+    ) -> Result<Self, TextListError> {
+        self.set_items_iter(iter)?;
+        Ok(self)
+    }
+
+    pub fn set_items_iter<I: IntoIterator<Item = D>, D: Display>(
+        &mut self,
+        iter: I,
+    ) -> Result<(), TextListError> {
+        self.items.clear();
+        let sanitize = self.sanitize.then_some(self.tab_width);
+        self.items.extend(iter.into_iter().map(|item| {
+            let item = format!("{}", item);
+            match sanitize {
+                Some(tab_width) => Self::sanitized(&item, tab_width),
+                None => item,
+            }
+        }));
+        self.selected = self.selected.min(self.items.len().saturating_sub(1));
         if self.height.is_some() {
             self.update()?;
         }
@@ -230,12 +893,22 @@ impl TextList {
         Ok(self)
     }
 
+    /// Select the item at `index`, an out of range index selects the last item
     pub fn set_selected(&mut self, index: usize) -> Result<(), TextListError> {
-        self.selected = index;
+        self.selected = index.min(self.items.len().saturating_sub(1));
         self.update()?;
         Ok(())
     }
 
+    /// Same as `set_selected()`, but errors instead of clamping an out of range index
+    pub fn try_set_selected(&mut self, index: usize) -> Result<(), TextListError> {
+        if !self.items.is_empty() && index >= self.items.len() {
+            return Err(TextListError::IndexOutOfBounds);
+        }
+
+        self.set_selected(index)
+    }
+
     pub fn non_ascii_replace(mut self, non_ascii_replace: char) -> Self {
         self.set_non_ascii_replace(non_ascii_replace);
         self
@@ -288,6 +961,30 @@ impl Default for TextList {
             ascii_only: false,
             non_ascii_replace: '?',
             trim_type: TrimType::FullTripleDot,
+            focused: true,
+            loading: false,
+            loading_text: String::from("Loading..."),
+            loading_style: Style::default(),
+            overflow_markers: false,
+            overflow_marker_style: Style::default(),
+            pending: None,
+            pending_count: None,
+            center_selection: false,
+            page_overlap: 2,
+            separators_after: HashSet::new(),
+            separator_symbol: '─',
+            separator_style: Style::default(),
+            compact_fallback: false,
+            sanitize: false,
+            tab_width: 4,
+            collapsed: false,
+            on_near_end: None,
+            near_end_threshold: 1,
+            scrollbar: false,
+            cursor_title: None,
+            show_position_title: false,
+            wrap_mode: WrapMode::default(),
+            width: None,
         }
     }
 }
@@ -296,63 +993,94 @@ impl Default for TextList {
 impl Widget for TextList {
     /// Note that if `self.height` does not match the actualy height, it will panic instead because
     /// there is no way to return a `Result<T, E>` out of this function
-    fn render(mut self, area: ratatui::layout::Rect, buf: &mut ratatui::buffer::Buffer) {
+    fn render(self, area: ratatui::layout::Rect, buf: &mut ratatui::buffer::Buffer) {
+        self.render_ref(area, buf);
+    }
+}
+
+impl TextList {
+    /// Same as `Widget::render()` but without taking ownership, only the items on screen are
+    /// copied
+    pub fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        if self.collapsed {
+            self.render_collapsed(area, buf);
+            return;
+        }
+
         let height = self.height.expect("unknown height");
         if height != area.height {
             panic!("height mismatch");
         }
 
+        if self.wrap_mode == WrapMode::Wrap && self.width != Some(area.width) {
+            panic!("width mismatch");
+        }
+
+        if self.loading {
+            buf.set_style(area, self.style);
+
+            let width = (self.loading_text.width() as u16).min(area.width);
+            buf.set_stringn(
+                area.x + (area.width - width) / 2,
+                area.y + area.height / 2,
+                &self.loading_text,
+                area.width as usize,
+                self.loading_style,
+            );
+            return;
+        }
+
         if area.height < 3 {
             // panic!("insufficient height");
+            if self.compact_fallback {
+                buf.set_style(area, self.style);
+                if let Some(item) = self.items.get(self.selected) {
+                    buf.set_stringn(
+                        area.x,
+                        area.y,
+                        self.trimmed(self.display_item(item), area.width as usize),
+                        area.width as usize,
+                        self.selected_style,
+                    );
+                }
+            }
             return;
         }
 
-        self.items = self
+        let items_len = self.items.len();
+        let visible_count = self.visible_count(self.item_rows_in(height));
+        let separators_after = &self.separators_after;
+        let items = self
             .items
-            .into_iter()
+            .iter()
             .skip(self.scroll)
-            .take(height as usize - 2)
-            .collect();
-
-        // remove non ascii character
-
-        if self.ascii_only {
-            self.items.iter_mut().for_each(|item| {
-                *item = item
-                    .chars()
-                    .map(|c| {
-                        if c.is_ascii() {
-                            c
-                        } else {
-                            self.non_ascii_replace
-                        }
-                    })
-                    .collect();
-            });
-        }
+            .take(visible_count)
+            .map(|item| self.display_item(item))
+            .collect::<Vec<_>>();
+        let more_above = self.scroll > 0;
+        let more_below = self.scroll + items.len() < items_len;
+
+        let area = if self.scrollbar {
+            self.render_scrollbar(area, buf, items.len());
+            Rect {
+                width: area.width.saturating_sub(1),
+                ..area
+            }
+        } else {
+            area
+        };
 
         // check if item is too long
 
         let width_from = area.width as usize - 2;
-        let (width_after, end_with) = match self.trim_type {
-            TrimType::None => (width_from, ""),
-            TrimType::FullTripleDot => (width_from - 3, "..."),
-            TrimType::ShortTripleDot => (width_from - 1, "…"),
-        };
 
-        if area.width as usize - 2 < end_with.chars().count() {
+        if area.width as usize - 2 < self.trim_type.end_with().chars().count() {
             panic!("width too small");
         }
 
-        self.items.iter_mut().for_each(|item| {
-            let chars = UnicodeSegmentation::graphemes(item.as_str(), true).collect::<Vec<_>>();
-            if chars.len() > width_from {
-                *item = format!(
-                    "{}{}",
-                    chars.into_iter().take(width_after).collect::<String>(),
-                    end_with
-                );
-            }
+        let items = items.into_iter().map(|item| match self.wrap_mode {
+            WrapMode::Truncate => vec![self.trimmed(item, width_from)],
+            WrapMode::Wrap => Self::wrap(&item, width_from),
         });
 
         // setting background style for rect
@@ -362,31 +1090,144 @@ impl Widget for TextList {
         // render items
 
         let mut y = area.y;
-        self.items
+        items
             .into_iter()
             .zip(self.scroll..)
-            .for_each(|(item, index)| {
-                if index == self.selected {
-                    let block = Block::default()
+            .for_each(|(lines, index)| {
+                if index == self.selected && self.focused {
+                    let mut block = Block::default()
                         .border_type(self.border_type)
                         .border_style(self.cursor_style)
                         .borders(Borders::ALL);
-                    let paragraph = Paragraph::new(item).style(self.selected_style).block(block);
+                    if let Some(title) = self.cursor_box_title() {
+                        block = block.title(title);
+                    }
+                    let box_height = lines.len() as u16 + 2;
+                    let paragraph =
+                        Paragraph::new(lines.into_iter().map(Line::from).collect::<Vec<_>>())
+                            .style(self.selected_style)
+                            .block(block);
 
                     let select_area = Rect {
                         x: area.x,
                         y,
-                        height: 3,
+                        height: box_height.min(area.bottom().saturating_sub(y)),
                         width: area.width,
                     };
 
                     paragraph.render(select_area, buf);
-                    y += 3;
+                    y += box_height;
                 } else {
-                    buf.set_string(area.x + 1, y, item, Style::default());
+                    let style = if index == self.selected {
+                        self.selected_style
+                    } else {
+                        Style::default()
+                    };
+
+                    for line in lines {
+                        if y >= area.bottom() {
+                            break;
+                        }
+                        buf.set_string(area.x + 1, y, line, style);
+                        y += 1;
+                    }
+                }
+
+                if separators_after.contains(&index) && y < area.bottom() {
+                    buf.set_string(
+                        area.x + 1,
+                        y,
+                        self.separator_symbol
+                            .to_string()
+                            .repeat(area.width.saturating_sub(2) as usize),
+                        self.separator_style,
+                    );
                     y += 1;
                 }
-            })
+            });
+
+        // overflow markers
+
+        if self.overflow_markers {
+            if more_above {
+                buf.set_string(area.right() - 1, area.y, "▲", self.overflow_marker_style);
+            }
+
+            if more_below {
+                buf.set_string(
+                    area.right() - 1,
+                    area.bottom() - 1,
+                    "▼",
+                    self.overflow_marker_style,
+                );
+            }
+        }
+    }
+
+    /// Title of the cursor box, `cursor_title` goes before the position title
+    fn cursor_box_title(&self) -> Option<String> {
+        match &self.cursor_title {
+            Some(title) => Some(title.clone()),
+            None if self.show_position_title => {
+                Some(format!("{}/{}", self.selected + 1, self.items.len()))
+            }
+            None => None,
+        }
+    }
+
+    /// Scrollbar on the last column of `area` with `visible` items on screen, nothing is drawn if
+    /// every item is on screen
+    fn render_scrollbar(&self, area: Rect, buf: &mut Buffer, visible: usize) {
+        let track = Rect {
+            x: area.right().saturating_sub(1),
+            width: area.width.min(1),
+            ..area
+        };
+        buf.set_style(track, self.style);
+
+        let total = self.items.len();
+        if visible >= total {
+            return;
+        }
+
+        let height = track.height as usize;
+        let thumb_height = (height * visible / total).max(1);
+        let thumb_start = if self.scroll + visible >= total {
+            height - thumb_height
+        } else {
+            (height * self.scroll / total).min(height - thumb_height)
+        };
+
+        for (offset, y) in (track.top()..track.bottom()).enumerate() {
+            let symbol = if (thumb_start..thumb_start + thumb_height).contains(&offset) {
+                "█"
+            } else {
+                "│"
+            };
+            buf.set_string(track.x, y, symbol, self.style);
+        }
+    }
+
+    /// The selected item with a `▾` marker at the end, on the first row of `area`
+    fn render_collapsed(&self, area: Rect, buf: &mut Buffer) {
+        if area.height == 0 || area.width == 0 {
+            return;
+        }
+
+        let row = Rect { height: 1, ..area };
+        buf.set_style(row, self.style);
+
+        if let Some(item) = self.items.get(self.selected) {
+            let width = row.width.saturating_sub(2) as usize;
+            buf.set_stringn(
+                row.x,
+                row.y,
+                self.trimmed(self.display_item(item), width),
+                width,
+                self.selected_style,
+            );
+        }
+        buf.set_string(row.right() - 1, row.y, "▾", self.style);
     }
 }
 
@@ -397,6 +1238,8 @@ pub enum TextListError {
     UnknownHeight,
     /// Not enough height to draw the text list widget (the minimal height is 3)
     NotEnoughHeight,
+    /// Selecting an index that is not less than the number of items
+    IndexOutOfBounds,
 }
 
 impl Display for TextListError {
@@ -407,6 +1250,19 @@ impl Display for TextListError {
 
 impl Error for TextListError {}
 
+/// How items wider than the text list are drawn
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WrapMode {
+    /// Trim the item with `TextList.trim_type`
+    #[default]
+    Truncate,
+    /// Wrap the item to as many rows as needed, `TextList.width` has to be set
+    Wrap,
+}
+
+/// Callback for `TextList.on_near_end`
+pub type NearEndCallback = Box<dyn FnMut() -> Vec<String>>;
+
 /// How to handle items that are longer than the avaliable width
 #[derive(Debug, Clone, Copy)]
 pub enum TrimType {
@@ -416,4 +1272,17 @@ pub enum TrimType {
     FullTripleDot,
     /// Add nothing to the end of item
     r#None,
+    /// Keep the end of item visible and add `'…'` to the start
+    LeadingEllipsis,
+}
+
+impl TrimType {
+    /// The string added to a trimmed item
+    fn end_with(self) -> &'static str {
+        match self {
+            Self::None => "",
+            Self::FullTripleDot => "...",
+            Self::ShortTripleDot | Self::LeadingEllipsis => "…",
+        }
+    }
 }